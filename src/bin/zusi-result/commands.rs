@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use time::Duration;
+use zusi_result_lib::chart::ChartOptions;
+use zusi_result_lib::export::CsvOptions;
+use zusi_result_lib::html::HtmlOptions;
+use zusi_result_lib::loader::{LoadedResult, RESULT_FILE_SUFFIX};
+use zusi_result_lib::partition::route;
+use zusi_result_lib::result_analyser::{AnalyseError, ResultAnalyser};
+use zusi_result_lib::result_analyser_group::ResultAnalyserGroup;
+
+use crate::output::{Formatter, Table, Unit};
+
+pub type Group = ResultAnalyserGroup<ResultAnalyser<LoadedResult>, LoadedResult>;
+
+/// Name of the run in all tables.
+fn run_name(analyser: &ResultAnalyser<LoadedResult>) -> String {
+    let path = &analyser.result().path;
+    path.file_name().map(Path::new).unwrap_or(path).display().to_string()
+}
+
+fn cell<T, F: FnOnce(T) -> String>(value: Result<T, AnalyseError>, format: F) -> Option<String> {
+    value.ok().map(format)
+}
+
+/// Per-run metrics and a final row with the metrics of the whole group.
+pub fn summary(group: &Group, formatter: &Formatter) -> Table {
+    let speed_unit = formatter.speed_unit().symbol();
+    let distance_unit = formatter.distance_unit().symbol();
+    let mut table = Table::new(vec![
+        "run".into(),
+        "train".into(),
+        format!("distance [{distance_unit}]"),
+        formatter.duration_header("driving time"),
+        formatter.duration_header("pure driving time"),
+        format!("average speed [{speed_unit}]"),
+        format!("pure average speed [{speed_unit}]"),
+        "energy [kWh]".into(),
+    ]);
+
+    for analyser in group.analysers() {
+        table.push(vec![
+            Some(run_name(analyser)),
+            Some(analyser.result().result.zugnummer.clone()),
+            cell(analyser.distance(), |distance| formatter.distance(distance)),
+            cell(analyser.driving_time(), |duration| formatter.duration(duration)),
+            cell(analyser.pure_driving_time(), |duration| formatter.duration(duration)),
+            cell(analyser.average_speed(), |speed| formatter.speed(speed)),
+            cell(analyser.pure_average_speed(), |speed| formatter.speed(speed)),
+            Some(formatter.energy(analyser.energy_consumption())),
+        ]);
+    }
+
+    table.push(vec![
+        Some(format!("all ({} runs)", group.analysers().len())),
+        None,
+        cell(group.total_distance(), |distance| formatter.distance(distance)),
+        cell(group.total_driving_time(), |duration| formatter.duration(duration)),
+        cell(group.total_pure_driving_time(), |duration| formatter.duration(duration)),
+        cell(group.average_speed(), |speed| formatter.speed(speed)),
+        cell(group.pure_average_speed(), |speed| formatter.speed(speed)),
+        Some(formatter.energy(group.total_energy_consumption())),
+    ]);
+
+    table
+}
+
+/// One row per run with train number, date and the first and last timetable station.
+pub fn runs(group: &Group, formatter: &Formatter) -> Table {
+    let mut table = Table::new(vec![
+        "run".into(),
+        "train".into(),
+        "date".into(),
+        "from".into(),
+        "to".into(),
+    ]);
+
+    for analyser in group.analysers() {
+        let result = &analyser.result().result;
+        let route = route(analyser);
+        table.push(vec![
+            Some(run_name(analyser)),
+            Some(result.zugnummer.clone()),
+            Some(formatter.datetime(result.datum)),
+            route.as_ref().map(|route| route.from.clone()),
+            route.as_ref().map(|route| route.to.clone()),
+        ]);
+    }
+
+    table
+}
+
+/// One row per timetable stop with scheduled and actual times.
+/// Runs without timetable stops are skipped.
+pub fn stops(group: &Group, formatter: &Formatter) -> Table {
+    let mut table = Table::new(vec![
+        "run".into(),
+        "station".into(),
+        "scheduled arrival".into(),
+        "arrival".into(),
+        formatter.duration_header("arrival delay"),
+        "scheduled departure".into(),
+        "departure".into(),
+        formatter.duration_header("departure delay"),
+    ]);
+
+    for analyser in group.analysers() {
+        // the threshold does not influence the listed stops
+        let Ok(punctuality) = analyser.punctuality(Duration::ZERO) else {
+            continue;
+        };
+
+        for stop in punctuality.stops.iter() {
+            table.push(vec![
+                Some(run_name(analyser)),
+                Some(stop.station.clone()),
+                stop.scheduled_arrival.map(|datetime| formatter.datetime(datetime)),
+                stop.actual_arrival.map(|datetime| formatter.datetime(datetime)),
+                stop.arrival_delay().map(|duration| formatter.duration(duration)),
+                stop.scheduled_departure.map(|datetime| formatter.datetime(datetime)),
+                stop.actual_departure.map(|datetime| formatter.datetime(datetime)),
+                stop.departure_delay().map(|duration| formatter.duration(duration)),
+            ]);
+        }
+    }
+
+    table
+}
+
+/// One row per period above the effective speed limit.
+/// `tolerance` is given in the selected speed unit.
+pub fn speeding(group: &Group, formatter: &Formatter, tolerance: f32) -> Table {
+    let speed_unit = formatter.speed_unit().symbol();
+    let mut table = Table::new(vec![
+        "run".into(),
+        "start".into(),
+        "end".into(),
+        formatter.duration_header("duration"),
+        "start km".into(),
+        "end km".into(),
+        format!("peak overspeed [{speed_unit}]"),
+        "limited by".into(),
+    ]);
+
+    for analyser in group.analysers() {
+        let Ok(compliance) = analyser.speed_limit_compliance(formatter.speed_from_unit(tolerance)) else {
+            continue;
+        };
+
+        for interval in compliance.intervals.iter() {
+            table.push(vec![
+                Some(run_name(analyser)),
+                Some(formatter.datetime(interval.start)),
+                Some(formatter.datetime(interval.end)),
+                Some(formatter.duration(interval.duration())),
+                Some(formatter.kilometre(interval.start_km)),
+                Some(formatter.kilometre(interval.end_km)),
+                Some(formatter.speed(interval.peak_overspeed)),
+                Some(interval.source.name().into()),
+            ]);
+        }
+    }
+
+    table
+}
+
+/// Writes the CSV export of a single run to stdout, or of every run into `output_dir`.
+/// The file name of each export is the name of the result file with `.result.xml` replaced by `.csv`.
+/// Nothing is written if two runs would be exported to the same file, e.g. result files of the same name in different directories.
+pub fn export(group: &Group, unit: Unit, include_events: bool, output_dir: Option<&Path>) -> io::Result<()> {
+    let options = CsvOptions {
+        include_events,
+        speed_unit: unit.speed_unit(),
+        distance_unit: unit.distance_unit(),
+    };
+
+    let Some(output_dir) = output_dir else {
+        return match group.analysers() {
+            [analyser] => analyser.write_csv(&options, io::stdout().lock()),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "multiple runs can only be exported with --output-dir")),
+        };
+    };
+
+    let mut sources: HashMap<String, &Path> = HashMap::new();
+    let mut file_names = vec![];
+    for analyser in group.analysers() {
+        let name = run_name(analyser);
+        let file_name = format!("{}.csv", name.strip_suffix(RESULT_FILE_SUFFIX).unwrap_or(&name));
+        let path = &analyser.result().path;
+        if let Some(other) = sources.insert(file_name.clone(), path) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} and {} would both be exported to {file_name}", other.display(), path.display()),
+            ));
+        }
+        file_names.push(file_name);
+    }
+
+    for (analyser, file_name) in group.analysers().iter().zip(file_names) {
+        let mut out = BufWriter::new(File::create(output_dir.join(file_name))?);
+        analyser.write_csv(&options, &mut out)?;
+        out.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Writes the HTML report of a single run, or of the whole group if there are multiple runs, to `output` or stdout.
+pub fn html(group: &Group, unit: Unit, output: Option<&Path>) -> io::Result<()> {
+    let options = HtmlOptions {
+        chart: ChartOptions {
+            speed_unit: unit.speed_unit(),
+            distance_unit: unit.distance_unit(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut out: Box<dyn Write> = match output {
+        Some(output) => Box::new(BufWriter::new(File::create(output)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    match group.analysers() {
+        [analyser] => analyser.write_html(&options, &mut out)?,
+        _ => group.write_html(&options, &mut out)?,
+    }
+    out.flush()
+}
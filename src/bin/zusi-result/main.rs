@@ -0,0 +1,170 @@
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand};
+use zusi_result_lib::loader::{load, Source};
+use zusi_result_lib::result_analyser::ResultAnalyser;
+use zusi_result_lib::result_analyser_group::ResultAnalyserGroup;
+
+use crate::output::{Format, Formatter, Table, Unit};
+
+#[cfg(test)]
+mod tests;
+mod commands;
+mod output;
+
+/// Analyses `.result.xml` files generated by Zusi 3.
+#[derive(Parser, Debug)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Show distance, driving times, speeds and energy consumption per run and for all runs.
+    Summary(Options),
+    /// List the runs with train number, date and first and last station.
+    Runs(Options),
+    /// List all timetable stops with scheduled and actual times.
+    Stops(Options),
+    /// List all periods above the speed limit.
+    Speeding {
+        #[command(flatten)]
+        options: Options,
+
+        /// Speed above the limit which is not counted as speeding, in the selected unit.
+        #[arg(short, long, default_value_t = 0.)]
+        tolerance: f32,
+    },
+    /// Export the measurement samples of each run as CSV.
+    Export {
+        #[command(flatten)]
+        input: Input,
+
+        /// Unit for speeds and distances.
+        #[arg(short, long, value_enum, default_value_t = Unit::Kmh)]
+        unit: Unit,
+
+        /// Add event-only entries and the columns `fahrt_typ`, `fahrt_parameter` and `fahrt_text`.
+        #[arg(short, long)]
+        events: bool,
+
+        /// Write one `<name>.csv` file per run into this directory instead of writing a single run to stdout.
+        #[arg(short, long)]
+        output_dir: Option<PathBuf>,
+    },
+    /// Write a self-contained HTML report of a single run or of all runs.
+    Html {
+        #[command(flatten)]
+        input: Input,
+
+        /// Unit for speeds and distances.
+        #[arg(short, long, value_enum, default_value_t = Unit::Kmh)]
+        unit: Unit,
+
+        /// Write the report into this file instead of stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+impl Command {
+    fn input(&self) -> &Input {
+        match self {
+            Command::Summary(options) | Command::Runs(options) | Command::Stops(options) => &options.input,
+            Command::Speeding { options, .. } => &options.input,
+            Command::Export { input, .. } | Command::Html { input, .. } => input,
+        }
+    }
+}
+
+/// The result files to analyse.
+#[derive(Args, Debug)]
+struct Input {
+    /// Files, directories or glob patterns, e.g. `data/**/*.result.xml`.
+    /// Directories are searched for files ending with `.result.xml`.
+    #[arg(required = true)]
+    paths: Vec<String>,
+
+    /// Search directories recursively.
+    #[arg(short, long)]
+    recursive: bool,
+}
+
+/// Options shared by all subcommands printing a table.
+#[derive(Args, Debug)]
+struct Options {
+    #[command(flatten)]
+    input: Input,
+
+    /// Unit for speeds and distances.
+    #[arg(short, long, value_enum, default_value_t = Unit::Kmh)]
+    unit: Unit,
+
+    /// Output format.
+    #[arg(short, long, value_enum, default_value_t = Format::Human)]
+    format: Format,
+}
+
+fn source(path: &str, recursive: bool) -> Source {
+    if path.contains(['*', '?', '[']) {
+        Source::Glob(path.into())
+    } else {
+        let path = PathBuf::from(path);
+        if path.is_dir() {
+            Source::Directory { path, recursive }
+        } else {
+            Source::File(path)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let input = cli.command.input();
+
+    let sources: Vec<Source> = input.paths.iter().map(|path| source(path, input.recursive)).collect();
+    let loaded = load(&sources);
+
+    for failure in loaded.failures.iter() {
+        eprintln!("{}: {}", failure.path.display(), failure.error);
+    }
+
+    let Ok(group) = ResultAnalyserGroup::new(loaded.results.into_iter().map(ResultAnalyser::new).collect()) else {
+        eprintln!("no results found");
+        return ExitCode::FAILURE;
+    };
+    #[cfg(feature = "parallel")]
+    group.par_summaries();
+
+    let written = match cli.command {
+        Command::Summary(options) => write_table(&options, |formatter| commands::summary(&group, formatter)),
+        Command::Runs(options) => write_table(&options, |formatter| commands::runs(&group, formatter)),
+        Command::Stops(options) => write_table(&options, |formatter| commands::stops(&group, formatter)),
+        Command::Speeding { options, tolerance } => write_table(&options, |formatter| commands::speeding(&group, formatter, tolerance)),
+        Command::Export { unit, events, output_dir, .. } => commands::export(&group, unit, events, output_dir.as_deref()),
+        Command::Html { unit, output, .. } => commands::html(&group, unit, output.as_deref()),
+    };
+
+    if let Err(error) = written {
+        eprintln!("{error}");
+        return ExitCode::FAILURE;
+    }
+
+    if loaded.failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn write_table<F: FnOnce(&Formatter) -> Table>(options: &Options, table: F) -> io::Result<()> {
+    let formatter = Formatter {
+        unit: options.unit,
+        format: options.format,
+    };
+    table(&formatter).write(options.format, &mut io::stdout().lock())
+}
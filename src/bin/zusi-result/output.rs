@@ -0,0 +1,185 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use time::{Duration, PrimitiveDateTime};
+use zusi_result_lib::units::{DistanceUnit, SpeedUnit};
+use zusi_result_lib::format::DATETIME_FORMAT;
+
+#[cfg(test)]
+mod tests;
+
+/// Units used for speeds and distances.
+#[derive(ValueEnum, PartialEq, Debug, Clone, Copy)]
+pub enum Unit {
+    /// Speeds in km/h, distances in km.
+    Kmh,
+    /// Speeds in m/s, distances in m.
+    Ms,
+}
+
+impl Unit {
+    pub fn speed_unit(&self) -> SpeedUnit {
+        match self {
+            Unit::Kmh => SpeedUnit::KilometresPerHour,
+            Unit::Ms => SpeedUnit::MetresPerSecond,
+        }
+    }
+
+    pub fn distance_unit(&self) -> DistanceUnit {
+        match self {
+            Unit::Kmh => DistanceUnit::Kilometres,
+            Unit::Ms => DistanceUnit::Metres,
+        }
+    }
+}
+
+#[derive(ValueEnum, PartialEq, Debug, Clone, Copy)]
+pub enum Format {
+    /// Aligned columns with rounded values.
+    Human,
+    /// Tab-separated values with a header line, unrounded values and durations in seconds.
+    Tsv,
+}
+
+/// Formats the cells of a [Table] according to the selected unit and format.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Formatter {
+    pub unit: Unit,
+    pub format: Format,
+}
+
+impl Formatter {
+    pub fn speed_unit(&self) -> SpeedUnit {
+        self.unit.speed_unit()
+    }
+
+    pub fn distance_unit(&self) -> DistanceUnit {
+        self.unit.distance_unit()
+    }
+
+    /// Adds the unit to the header of a duration column if durations are printed as plain seconds.
+    pub fn duration_header(&self, name: &str) -> String {
+        match self.format {
+            Format::Human => name.into(),
+            Format::Tsv => format!("{name} [s]"),
+        }
+    }
+
+    /// Converts a speed in the selected unit into m/s.
+    pub fn speed_from_unit(&self, speed: f32) -> f32 {
+        speed / self.speed_unit().convert(1.)
+    }
+
+    /// Formats a speed given in m/s.
+    pub fn speed(&self, speed: f32) -> String {
+        self.number(self.speed_unit().convert(speed), 1)
+    }
+
+    /// Formats a distance given in m.
+    pub fn distance(&self, distance: f32) -> String {
+        let precision = match self.distance_unit() {
+            DistanceUnit::Kilometres => 3,
+            DistanceUnit::Metres => 1,
+        };
+        self.number(self.distance_unit().convert(distance), precision)
+    }
+
+    /// Formats a track position given in km, independent of the selected unit.
+    pub fn kilometre(&self, kilometre: f32) -> String {
+        self.number(kilometre, 3)
+    }
+
+    pub fn energy(&self, energy: f32) -> String {
+        self.number(energy, 1)
+    }
+
+    pub fn duration(&self, duration: Duration) -> String {
+        match self.format {
+            Format::Human => duration.to_string(),
+            Format::Tsv => duration.whole_seconds().to_string(),
+        }
+    }
+
+    pub fn datetime(&self, datetime: PrimitiveDateTime) -> String {
+        datetime.format(DATETIME_FORMAT).unwrap()
+    }
+
+    fn number(&self, value: f32, precision: usize) -> String {
+        match self.format {
+            Format::Human => format!("{value:.precision$}"),
+            Format::Tsv => value.to_string(),
+        }
+    }
+}
+
+/// A table of optional cells, missing values are printed as `-` in human-readable output and left empty otherwise.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<Option<String>>>,
+}
+
+impl Table {
+    pub fn new(headers: Vec<String>) -> Table {
+        Self {
+            headers,
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<Option<String>>) {
+        debug_assert_eq!(row.len(), self.headers.len());
+        self.rows.push(row);
+    }
+
+    pub fn write<W: Write>(&self, format: Format, out: &mut W) -> io::Result<()> {
+        match format {
+            Format::Human => self.write_human(out),
+            Format::Tsv => self.write_tsv(out),
+        }
+    }
+
+    fn write_human<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let rows: Vec<Vec<&str>> = self.rows.iter()
+            .map(|row| row.iter().map(|cell| cell.as_deref().unwrap_or("-")).collect())
+            .collect();
+
+        let widths: Vec<usize> = self.headers.iter().enumerate()
+            .map(|(column, header)| rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([header.chars().count()])
+                .max()
+                .unwrap())
+            .collect();
+
+        let headers: Vec<&str> = self.headers.iter().map(String::as_str).collect();
+        write_aligned(out, &headers, &widths)?;
+        let separators: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        write_aligned(out, &separators.iter().map(String::as_str).collect::<Vec<&str>>(), &widths)?;
+        for row in rows {
+            write_aligned(out, &row, &widths)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_tsv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "{}", self.headers.join("\t"))?;
+        for row in self.rows.iter() {
+            let cells: Vec<String> = row.iter()
+                // tabs and line breaks would break the structure
+                .map(|cell| cell.as_deref().unwrap_or("").replace(['\t', '\n'], " "))
+                .collect();
+            writeln!(out, "{}", cells.join("\t"))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_aligned<W: Write>(out: &mut W, cells: &[&str], widths: &[usize]) -> io::Result<()> {
+    let line: Vec<String> = cells.iter().zip(widths)
+        .map(|(cell, width)| format!("{cell:<width$}"))
+        .collect();
+    writeln!(out, "{}", line.join("  ").trim_end())
+}
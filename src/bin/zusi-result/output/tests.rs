@@ -0,0 +1,47 @@
+use time::Duration;
+use time::macros::datetime;
+
+use crate::output::{Format, Formatter, Table, Unit};
+
+fn table() -> Table {
+    let mut table = Table::new(vec!["run".into(), "speed".into()]);
+    table.push(vec![Some("Ergebnis0.result.xml".into()), Some("12.5".into())]);
+    table.push(vec![Some("a\tb".into()), None]);
+    table
+}
+
+#[test]
+fn test_human_table() {
+    let mut out = vec![];
+    table().write(Format::Human, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "\
+run                   speed
+--------------------  -----
+Ergebnis0.result.xml  12.5
+a\tb                   -
+");
+}
+
+#[test]
+fn test_tsv_table() {
+    let mut out = vec![];
+    table().write(Format::Tsv, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "run\tspeed\nErgebnis0.result.xml\t12.5\na b\t\n");
+}
+
+#[test]
+fn test_formatter() {
+    let human = Formatter { unit: Unit::Kmh, format: Format::Human };
+    assert_eq!(human.speed(10.), "36.0");
+    assert_eq!(human.distance(1234.4), "1.234");
+    assert_eq!(human.duration(Duration::seconds(3723)), "1h2m3s");
+    assert_eq!(human.datetime(datetime!(2018-11-06 7:26)), "2018-11-06 07:26:00");
+    assert_eq!(human.speed_from_unit(36.), 10.);
+
+    let tsv = Formatter { unit: Unit::Ms, format: Format::Tsv };
+    assert_eq!(tsv.speed(10.25), "10.25");
+    assert_eq!(tsv.distance(1234.5), "1234.5");
+    assert_eq!(tsv.duration(Duration::seconds(3723)), "3723");
+    assert_eq!(tsv.speed_from_unit(36.), 36.);
+    assert_eq!(tsv.duration_header("duration"), "duration [s]");
+}
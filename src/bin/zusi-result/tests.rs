@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use clap::{CommandFactory, Parser};
+use time::macros::datetime;
+use zusi_result_lib::loader::LoadedResult;
+use zusi_result_lib::result_analyser::ResultAnalyser;
+use zusi_result_lib::result_analyser_group::ResultAnalyserGroup;
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+use crate::{Cli, Command};
+use crate::commands::{export, Group};
+use crate::output::{Format, Unit};
+
+#[test]
+fn test_cli() {
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn test_parse_options() {
+    let cli = Cli::try_parse_from(["zusi-result", "speeding", "-u", "ms", "-f", "tsv", "-t", "2", "-r", "data"]).unwrap();
+    let input = cli.command.input();
+    assert_eq!(input.paths, vec!["data"]);
+    assert!(input.recursive);
+    let Command::Speeding { options, tolerance } = cli.command else {
+        panic!("expected speeding");
+    };
+    assert_eq!(options.unit, Unit::Ms);
+    assert_eq!(options.format, Format::Tsv);
+    assert_eq!(tolerance, 2.);
+
+    assert!(Cli::try_parse_from(["zusi-result", "summary"]).is_err());
+}
+
+#[test]
+fn test_parse_export() {
+    let cli = Cli::try_parse_from(["zusi-result", "export", "-e", "-o", "out", "data"]).unwrap();
+    assert!(matches!(cli.command, Command::Export { events: true, unit: Unit::Kmh, output_dir: Some(_), .. }));
+    assert!(Cli::try_parse_from(["zusi-result", "export", "-f", "tsv", "data"]).is_err());
+}
+
+#[test]
+fn test_parse_html() {
+    let cli = Cli::try_parse_from(["zusi-result", "html", "-u", "ms", "-o", "report.html", "data"]).unwrap();
+    assert!(matches!(cli.command, Command::Html { unit: Unit::Ms, output: Some(_), .. }));
+    assert_eq!(cli.command.input().paths, vec!["data".to_string()]);
+}
+
+fn group(paths: &[&str]) -> Group {
+    ResultAnalyserGroup::new(paths.iter()
+        .map(|path| ResultAnalyser::new(LoadedResult {
+            path: PathBuf::from(path),
+            result: ZusiResult::builder()
+                .datum(datetime!(2019-01-01 23:14))
+                .value(vec![])
+                .build(),
+        }))
+        .collect()).unwrap()
+}
+
+#[test]
+fn test_export() {
+    let output_dir = tempfile::tempdir().unwrap();
+    export(&group(&["a/Ergebnis0.result.xml", "a/Ergebnis1.result.xml"]), Unit::Kmh, false, Some(output_dir.path())).unwrap();
+
+    assert!(output_dir.path().join("Ergebnis0.csv").is_file());
+    assert!(output_dir.path().join("Ergebnis1.csv").is_file());
+}
+
+#[test]
+fn test_export_duplicate_names() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let error = export(&group(&["a/Ergebnis0.result.xml", "b/Ergebnis0.result.xml"]), Unit::Kmh, false, Some(output_dir.path())).unwrap_err();
+
+    assert_eq!(error.to_string(), "a/Ergebnis0.result.xml and b/Ergebnis0.result.xml would both be exported to Ergebnis0.csv");
+    assert!(!output_dir.path().join("Ergebnis0.csv").exists());
+}
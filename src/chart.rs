@@ -0,0 +1,295 @@
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::events::{Event, EventKind};
+use crate::result_analyser::AnalyseError;
+use crate::samples::{is_measurement, NO_VALUE, Samples};
+use crate::units::{DistanceUnit, SpeedUnit};
+
+#[cfg(test)]
+mod tests;
+
+const MARGIN_LEFT: f32 = 60.;
+const MARGIN_RIGHT: f32 = 20.;
+const MARGIN_TOP: f32 = 20.;
+const MARGIN_BOTTOM: f32 = 45.;
+
+const RUN_COLOURS: [&str; 8] = ["#1f77b4", "#2ca02c", "#ff7f0e", "#17becf", "#8c564b", "#e377c2", "#bcbd22", "#393b79"];
+
+type LimitAttribute = fn(&FahrtEintrag) -> f32;
+
+/// Track, signal and train protection limit with their colour and label.
+const LIMITS: [(LimitAttribute, &str, &str); 3] = [
+    (|fahrt_eintrag| fahrt_eintrag.fahrt_speed_strecke, "#7f7f7f", "track limit"),
+    (|fahrt_eintrag| fahrt_eintrag.fahrt_speed_signal, "#d62728", "signal limit"),
+    (|fahrt_eintrag| fahrt_eintrag.fahrt_speed_zugsicherung, "#9467bd", "train protection limit"),
+];
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct ChartOptions {
+    /// Width of the whole image in pixels.
+    pub width: u32,
+    /// Height of the whole image in pixels.
+    pub height: u32,
+    pub speed_unit: SpeedUnit,
+    pub distance_unit: DistanceUnit,
+}
+
+impl Default for ChartOptions {
+    fn default() -> Self {
+        Self {
+            width: 1200,
+            height: 500,
+            speed_unit: SpeedUnit::KilometresPerHour,
+            distance_unit: DistanceUnit::Kilometres,
+        }
+    }
+}
+
+/// Renders the speed of a run over the distance as a standalone SVG image.
+/// For more details see [overlay_chart].
+///
+/// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
+pub fn speed_chart(result: &ZusiResult, options: &ChartOptions) -> Result<String, AnalyseError> {
+    overlay_chart([result], options)
+}
+
+/// Renders the speed of several runs of the same route over the distance as a standalone SVG image.
+///
+/// The distance of each run starts at its first measurement sample.
+/// The track, signal and train protection limits are drawn as step lines
+/// and the timetable stations are marked with their `fahrt_text`, both taken from the first run.
+///
+/// Throws [AnalyseError::NoEntries] if no results are given or a [ZusiResult] does not contain any measurement samples.
+pub fn overlay_chart<'a, I: IntoIterator<Item = &'a ZusiResult>>(results: I, options: &ChartOptions) -> Result<String, AnalyseError> {
+    let results: Vec<&ZusiResult> = results.into_iter().collect();
+    let runs = results.iter()
+        .map(|result| {
+            let samples = Samples::new(result);
+            let start = samples.first().ok_or(AnalyseError::NoEntries)?.fahrt_weg;
+            Ok((*result, samples, start))
+        })
+        .collect::<Result<Vec<(&ZusiResult, Samples, f32)>, AnalyseError>>()?;
+    let (first_result, first_samples, first_start) = runs.first().ok_or(AnalyseError::NoEntries)?;
+
+    let max_distance = runs.iter()
+        .map(|(_, samples, start)| options.distance_unit.convert(samples.last().unwrap().fahrt_weg - start))
+        .fold(0., f32::max);
+    let max_speed = runs.iter()
+        .flat_map(|(_, samples, _)| samples.iter())
+        .flat_map(|fahrt_eintrag| [
+            fahrt_eintrag.fahrt_speed,
+            fahrt_eintrag.fahrt_speed_strecke,
+            fahrt_eintrag.fahrt_speed_signal,
+            fahrt_eintrag.fahrt_speed_zugsicherung,
+        ])
+        .map(|speed| options.speed_unit.convert(speed))
+        .fold(0., f32::max);
+
+    let plot = Plot::new(options, max_distance, max_speed);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">\n\
+        <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n",
+        options.width, options.height,
+    );
+
+    plot.write_axes(&mut svg, options);
+
+    for (station_distance, station) in stations(first_result, *first_start) {
+        let x = plot.x(options.distance_unit.convert(station_distance));
+        svg.push_str(&format!(
+            "<line x1=\"{x:.1}\" y1=\"{top:.1}\" x2=\"{x:.1}\" y2=\"{bottom:.1}\" stroke=\"#bbbbbb\" stroke-dasharray=\"2 3\"/>\n\
+            <text transform=\"translate({tx:.1} {ty:.1}) rotate(-90)\" fill=\"#555555\">{station}</text>\n",
+            top = plot.top, bottom = plot.bottom, tx = x - 3., ty = plot.bottom - 4., station = escape(station),
+        ));
+    }
+
+    for (limit, colour, _) in LIMITS {
+        let path = limit_path(&plot, first_samples, *first_start, limit, options);
+        if !path.is_empty() {
+            svg.push_str(&format!("<path d=\"{path}\" fill=\"none\" stroke=\"{colour}\" stroke-width=\"1.5\" stroke-dasharray=\"6 3\"/>\n"));
+        }
+    }
+
+    for (index, (_, samples, start)) in runs.iter().enumerate() {
+        let path: Vec<String> = samples.iter()
+            .enumerate()
+            .map(|(sample, fahrt_eintrag)| format!(
+                "{}{:.1} {:.1}",
+                if sample == 0 { "M" } else { "L" },
+                plot.x(options.distance_unit.convert(fahrt_eintrag.fahrt_weg - start)),
+                plot.y(options.speed_unit.convert(fahrt_eintrag.fahrt_speed)),
+            ))
+            .collect();
+        svg.push_str(&format!(
+            "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/>\n",
+            path.join(" "), RUN_COLOURS[index % RUN_COLOURS.len()],
+        ));
+    }
+
+    let legend = LIMITS.iter()
+        .map(|(_, colour, label)| (*colour, label.to_string(), true))
+        .chain(runs.iter().enumerate().map(|(index, (result, _, _))| (
+            RUN_COLOURS[index % RUN_COLOURS.len()],
+            format!("{} {}", result.zugnummer, result.datum.date()),
+            false,
+        )));
+    for (entry, (colour, label, dashed)) in legend.enumerate() {
+        let y = plot.top + 10. + entry as f32 * 16.;
+        let x = plot.right - 200.;
+        svg.push_str(&format!(
+            "<line x1=\"{x:.1}\" y1=\"{y:.1}\" x2=\"{x2:.1}\" y2=\"{y:.1}\" stroke=\"{colour}\" stroke-width=\"1.5\"{dash}/>\n\
+            <text x=\"{tx:.1}\" y=\"{ty:.1}\">{label}</text>\n",
+            x2 = x + 20., tx = x + 26., ty = y + 4., label = escape(&label),
+            dash = if dashed { " stroke-dasharray=\"6 3\"" } else { "" },
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Maps distances and speeds in the selected units to pixels.
+struct Plot {
+    left: f32,
+    right: f32,
+    top: f32,
+    bottom: f32,
+    max_distance: f32,
+    max_speed: f32,
+    distance_step: f32,
+    speed_step: f32,
+}
+
+impl Plot {
+    fn new(options: &ChartOptions, max_distance: f32, max_speed: f32) -> Plot {
+        let distance_step = tick_step(max_distance);
+        let speed_step = tick_step(max_speed);
+        Self {
+            left: MARGIN_LEFT,
+            right: (options.width as f32 - MARGIN_RIGHT).max(MARGIN_LEFT + 1.),
+            top: MARGIN_TOP,
+            bottom: (options.height as f32 - MARGIN_BOTTOM).max(MARGIN_TOP + 1.),
+            max_distance: (max_distance / distance_step).ceil().max(1.) * distance_step,
+            max_speed: (max_speed / speed_step).ceil().max(1.) * speed_step,
+            distance_step,
+            speed_step,
+        }
+    }
+
+    fn x(&self, distance: f32) -> f32 {
+        self.left + distance / self.max_distance * (self.right - self.left)
+    }
+
+    fn y(&self, speed: f32) -> f32 {
+        self.bottom - speed / self.max_speed * (self.bottom - self.top)
+    }
+
+    fn write_axes(&self, svg: &mut String, options: &ChartOptions) {
+        let distance_precision = tick_precision(self.distance_step);
+        for tick in 0..=(self.max_distance / self.distance_step).round() as u32 {
+            let distance = tick as f32 * self.distance_step;
+            let x = self.x(distance);
+            svg.push_str(&format!(
+                "<line x1=\"{x:.1}\" y1=\"{top:.1}\" x2=\"{x:.1}\" y2=\"{bottom:.1}\" stroke=\"#eeeeee\"/>\n\
+                <text x=\"{x:.1}\" y=\"{ty:.1}\" text-anchor=\"middle\">{distance:.distance_precision$}</text>\n",
+                top = self.top, bottom = self.bottom, ty = self.bottom + 16.,
+            ));
+        }
+
+        let speed_precision = tick_precision(self.speed_step);
+        for tick in 0..=(self.max_speed / self.speed_step).round() as u32 {
+            let speed = tick as f32 * self.speed_step;
+            let y = self.y(speed);
+            svg.push_str(&format!(
+                "<line x1=\"{left:.1}\" y1=\"{y:.1}\" x2=\"{right:.1}\" y2=\"{y:.1}\" stroke=\"#eeeeee\"/>\n\
+                <text x=\"{tx:.1}\" y=\"{ty:.1}\" text-anchor=\"end\">{speed:.speed_precision$}</text>\n",
+                left = self.left, right = self.right, tx = self.left - 6., ty = y + 4.,
+            ));
+        }
+
+        svg.push_str(&format!(
+            "<rect x=\"{left:.1}\" y=\"{top:.1}\" width=\"{width:.1}\" height=\"{height:.1}\" fill=\"none\" stroke=\"#333333\"/>\n\
+            <text x=\"{cx:.1}\" y=\"{ly:.1}\" text-anchor=\"middle\">distance [{distance_unit}]</text>\n\
+            <text transform=\"translate(14 {cy:.1}) rotate(-90)\" text-anchor=\"middle\">speed [{speed_unit}]</text>\n",
+            left = self.left, top = self.top, width = self.right - self.left, height = self.bottom - self.top,
+            cx = (self.left + self.right) / 2., ly = self.bottom + 36., cy = (self.top + self.bottom) / 2.,
+            distance_unit = options.distance_unit.symbol(), speed_unit = options.speed_unit.symbol(),
+        ));
+    }
+}
+
+/// Chooses a step of 1, 2 or 5 times a power of ten resulting in about 10 ticks.
+fn tick_step(max: f32) -> f32 {
+    if max <= 0. {
+        return 1.;
+    }
+    let rough = max / 10.;
+    let magnitude = 10f32.powf(rough.log10().floor());
+    [1., 2., 5., 10.].into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap()
+}
+
+/// Number of decimal places needed to print multiples of `step`.
+fn tick_precision(step: f32) -> usize {
+    (-step.log10().floor()).max(0.) as usize
+}
+
+/// Draws a limit as a step line which holds its value until the next measurement sample.
+/// The line is interrupted where the limit is not set.
+fn limit_path(plot: &Plot, samples: &Samples, start: f32, limit: LimitAttribute, options: &ChartOptions) -> String {
+    let mut path = vec![];
+    let mut open = false;
+
+    for (current, next) in samples.pairs() {
+        let speed = limit(current);
+        if speed == NO_VALUE {
+            open = false;
+            continue;
+        }
+
+        let y = plot.y(options.speed_unit.convert(speed));
+        let x1 = plot.x(options.distance_unit.convert(current.fahrt_weg - start));
+        let x2 = plot.x(options.distance_unit.convert(next.fahrt_weg - start));
+        path.push(format!("{}{x1:.1} {y:.1} L{x2:.1} {y:.1}", if open { "L" } else { "M" }));
+        open = true;
+    }
+
+    path.join(" ")
+}
+
+/// Finds the distance of all timetable stations relative to `start`.
+/// Event-only entries are placed at the last measurement sample, repeated entries of the same station are skipped.
+/// Timetable rows without a station are skipped, see [EventKind::is_timetable_stop].
+fn stations(result: &ZusiResult, start: f32) -> Vec<(f32, &str)> {
+    let mut stations: Vec<(f32, &str)> = vec![];
+    let mut fahrt_weg = start;
+
+    for value in result.value.iter() {
+        let ResultValue::FahrtEintrag(fahrt_eintrag) = value;
+        if is_measurement(fahrt_eintrag) {
+            fahrt_weg = fahrt_eintrag.fahrt_weg;
+        }
+
+        let station = match Event::from_fahrt_eintrag(fahrt_eintrag).map(|event| event.kind).filter(EventKind::is_timetable_stop) {
+            Some(EventKind::TimetableArrival { station }) | Some(EventKind::TimetableDeparture { station }) => station,
+            _ => continue,
+        };
+        if stations.last().is_some_and(|(_, last)| *last == station) {
+            continue;
+        }
+        stations.push((fahrt_weg - start, station));
+    }
+
+    stations
+}
+
+/// Escapes the characters with a special meaning in XML and HTML.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
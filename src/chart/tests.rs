@@ -0,0 +1,121 @@
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::chart::{ChartOptions, overlay_chart, speed_chart, stations, tick_step};
+use crate::result_analyser::AnalyseError;
+use crate::units::{DistanceUnit, SpeedUnit};
+
+fn result(zugnummer: &str) -> ZusiResult {
+    ZusiResult::builder()
+        .zugnummer(zugnummer.into())
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(0.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .fahrt_text("Bahnhof <A> & B".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01))
+                .fahrt_speed(15.)
+                .fahrt_speed_strecke(20.)
+                .fahrt_speed_signal(10.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:02))
+                .fahrt_speed(10.)
+                .fahrt_speed_strecke(30.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+        ])
+        .build()
+}
+
+#[test]
+fn test_speed_chart() {
+    let options = ChartOptions {
+        width: 800,
+        height: 300,
+        ..Default::default()
+    };
+    let svg = speed_chart(&result("12345"), &options).unwrap();
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\" height=\"300\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert!(svg.contains("Bahnhof &lt;A&gt; &amp; B"));
+    assert!(svg.contains("distance [km]"));
+    assert!(svg.contains("speed [km/h]"));
+    assert!(svg.contains("12345 2019-01-01"));
+    // one speed line, track and signal limit
+    assert_eq!(svg.matches("<path").count(), 3);
+}
+
+#[test]
+fn test_stations_skip_rows_without_station() {
+    let mut result = result("12345");
+    result.value.insert(2, ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_typ(2)
+        .fahrt_weg(-1.)
+        .fahrt_zeit(datetime!(2019-01-01 23:01))
+        .fahrt_speed(-1.)
+        .fahrt_text("".into())
+        .build()));
+
+    let stations = stations(&result, 100.);
+    assert_eq!(stations, vec![(0., "Bahnhof <A> & B")]);
+    assert!(stations.iter().all(|(_, station)| !station.is_empty()));
+}
+
+#[test]
+fn test_speed_chart_units() {
+    let options = ChartOptions {
+        speed_unit: SpeedUnit::MetresPerSecond,
+        distance_unit: DistanceUnit::Metres,
+        ..Default::default()
+    };
+    let svg = speed_chart(&result("12345"), &options).unwrap();
+
+    assert!(svg.contains("distance [m]"));
+    assert!(svg.contains("speed [m/s]"));
+    assert!(svg.contains(">2000</text>"));
+}
+
+#[test]
+fn test_overlay_chart() {
+    let first = result("1");
+    let second = result("2");
+    let svg = overlay_chart([&first, &second], &ChartOptions::default()).unwrap();
+
+    assert_eq!(svg.matches("<path").count(), 4);
+    assert!(svg.contains(">1 2019-01-01</text>"));
+    assert!(svg.contains(">2 2019-01-01</text>"));
+}
+
+#[test]
+fn test_chart_errors() {
+    let empty = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    assert_eq!(speed_chart(&empty, &ChartOptions::default()), Err(AnalyseError::NoEntries));
+    assert_eq!(overlay_chart([], &ChartOptions::default()), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_tick_step() {
+    assert_eq!(tick_step(0.), 1.);
+    assert_eq!(tick_step(160.), 20.);
+    assert_eq!(tick_step(2.), 0.2);
+    assert_eq!(tick_step(300.), 50.);
+}
@@ -0,0 +1,329 @@
+use time::Duration;
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+use crate::result_analyser::{AnalyseError, ResultAnalyser};
+use crate::samples::Samples;
+
+#[cfg(test)]
+mod tests;
+
+/// Difference between the distance and the change in `fahrt_km` of two samples up to which they are considered continuous, in m.
+/// Larger differences are jumps in the kilometre posts, e.g. at the border between two lines.
+const KILOMETRE_TOLERANCE: f32 = 10.;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct ComparisonOptions {
+    /// Distance between two points of the time-delta curve, in m.
+    pub resolution: f32,
+    /// Length of the sections the route is divided into for finding gains and losses, in m.
+    pub section_length: f32,
+}
+
+impl Default for ComparisonOptions {
+    fn default() -> Self {
+        Self {
+            resolution: 10.,
+            section_length: 1000.,
+        }
+    }
+}
+
+/// How the start of the compared run was matched to the reference run.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Alignment {
+    /// The later start of both runs was located in the other run by its `fahrt_km`.
+    Kilometre,
+    /// Both runs are assumed to start at the same point, distances are offsets from their first samples.
+    Distance,
+}
+
+/// The time delta of the compared run at a position on the common route.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct DeltaPoint {
+    /// Distance from the common start in m.
+    pub position: f32,
+    /// `fahrt_km` of the reference run at this position.
+    pub kilometre: f32,
+    /// Time the compared run needed from the common start minus the time of the reference run.
+    /// Positive values mean the compared run is behind.
+    pub delta: Duration,
+}
+
+/// A part of the common route of a fixed length.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct SectionDifference {
+    /// Distance of the start from the common start in m.
+    pub start: f32,
+    /// Distance of the end from the common start in m.
+    pub end: f32,
+    pub start_km: f32,
+    pub end_km: f32,
+    /// Change of the time delta within the section, negative values are gains of the compared run.
+    pub difference: Duration,
+}
+
+/// Running times of both runs between the same two timetable stops, see [Segment](crate::segments::Segment).
+#[derive(PartialEq, Debug, Clone)]
+pub struct SegmentDifference {
+    pub from: String,
+    pub to: String,
+    pub reference_time: Duration,
+    pub compared_time: Duration,
+}
+
+impl SegmentDifference {
+    /// Positive values mean the compared run was slower.
+    pub fn difference(&self) -> Duration {
+        self.compared_time - self.reference_time
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Comparison {
+    pub alignment: Alignment,
+    /// Length of the route driven by both runs in m.
+    pub distance: f32,
+    /// Time delta from the common start to the common end in steps of [ComparisonOptions::resolution].
+    pub curve: Vec<DeltaPoint>,
+    pub sections: Vec<SectionDifference>,
+    /// Segments of the reference run which are also contained in the compared run, in the order of the reference run.
+    pub segments: Vec<SegmentDifference>,
+}
+
+impl Comparison {
+    /// Time delta at the common end.
+    pub fn total_delta(&self) -> Duration {
+        self.curve.last().map(|point| point.delta).unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns up to `count` sections in which the compared run gained the most time, starting with the biggest gain.
+    pub fn biggest_gains(&self, count: usize) -> Vec<&SectionDifference> {
+        let mut gains: Vec<&SectionDifference> = self.sections.iter()
+            .filter(|section| section.difference.is_negative())
+            .collect();
+        gains.sort_by_key(|section| section.difference);
+        gains.truncate(count);
+        gains
+    }
+
+    /// Returns up to `count` sections in which the compared run lost the most time, starting with the biggest loss.
+    pub fn biggest_losses(&self, count: usize) -> Vec<&SectionDifference> {
+        let mut losses: Vec<&SectionDifference> = self.sections.iter()
+            .filter(|section| section.difference.is_positive())
+            .collect();
+        losses.sort_by_key(|section| -section.difference);
+        losses.truncate(count);
+        losses
+    }
+}
+
+/// Compares a run with a reference run of the same route.
+///
+/// The runs are aligned at the later of both starts: its `fahrt_km` is searched in the other run,
+/// preferring the match closest to the start of that run.
+/// If one of the runs does not contain kilometre posts, both runs are aligned at their first samples.
+/// Afterward positions are derived from `fahrt_weg`, which is not affected by jumps in the kilometre posts.
+/// The comparison ends where the shorter of both runs ends.
+///
+/// Throws [AnalyseError::InvalidOptions] if [ComparisonOptions::resolution] or [ComparisonOptions::section_length] is not positive,
+/// [AnalyseError::NoEntries] if a [ZusiResult] does not contain any measurement samples
+/// and [AnalyseError::ZeroDistance] if the runs do not share any part of the route,
+/// including runs with kilometre posts where the start of neither run is found in the other one.
+pub fn compare<R1, R2>(
+    reference: &ResultAnalyser<R1>, compared: &ResultAnalyser<R2>, options: &ComparisonOptions,
+) -> Result<Comparison, AnalyseError>
+where
+    R1: AsRef<ZusiResult>,
+    R2: AsRef<ZusiResult>,
+{
+    if !(options.resolution > 0. && options.section_length > 0.) {
+        return Err(AnalyseError::InvalidOptions);
+    }
+
+    let reference_track = Track::new(&reference.samples())?;
+    let compared_track = Track::new(&compared.samples())?;
+
+    let (alignment, reference_start, compared_start) = align(&reference_track, &compared_track)?;
+    let distance = (reference_track.end() - reference_start).min(compared_track.end() - compared_start);
+    if distance <= 0. {
+        return Err(AnalyseError::ZeroDistance);
+    }
+
+    let reference_offset = reference_track.seconds_at(reference_start);
+    let compared_offset = compared_track.seconds_at(compared_start);
+    let point = |position: f32| DeltaPoint {
+        position,
+        kilometre: reference_track.kilometre_at(reference_start + position),
+        delta: Duration::seconds_f64(
+            (compared_track.seconds_at(compared_start + position) - compared_offset)
+                - (reference_track.seconds_at(reference_start + position) - reference_offset),
+        ),
+    };
+
+    let curve: Vec<DeltaPoint> = positions(distance, options.resolution).map(point).collect();
+
+    let boundaries: Vec<DeltaPoint> = positions(distance, options.section_length).map(point).collect();
+    let sections = boundaries.windows(2)
+        .map(|pair| SectionDifference {
+            start: pair[0].position,
+            end: pair[1].position,
+            start_km: pair[0].kilometre,
+            end_km: pair[1].kilometre,
+            difference: pair[1].delta - pair[0].delta,
+        })
+        .collect();
+
+    Ok(Comparison {
+        alignment,
+        distance,
+        curve,
+        sections,
+        segments: segment_differences(reference, compared),
+    })
+}
+
+/// Multiples of `step` from 0 up to `distance`, ending with `distance`.
+fn positions(distance: f32, step: f32) -> impl Iterator<Item = f32> {
+    let steps = (distance / step).ceil() as usize;
+    (0..=steps).map(move |index| (index as f32 * step).min(distance))
+}
+
+/// Matches the segments of both runs by their stations.
+fn segment_differences<R1, R2>(reference: &ResultAnalyser<R1>, compared: &ResultAnalyser<R2>) -> Vec<SegmentDifference>
+where
+    R1: AsRef<ZusiResult>,
+    R2: AsRef<ZusiResult>,
+{
+    let compared_segments = compared.segments();
+    let mut next = 0;
+
+    reference.segments().iter()
+        .filter_map(|segment| {
+            let reference_segment = segment.result();
+            let index = next + compared_segments[next..].iter().position(|candidate| {
+                let candidate = candidate.result();
+                candidate.from == reference_segment.from && candidate.to == reference_segment.to
+            })?;
+            next = index + 1;
+
+            Some(SegmentDifference {
+                from: reference_segment.from.clone(),
+                to: reference_segment.to.clone(),
+                reference_time: segment.driving_time().ok()?,
+                compared_time: compared_segments[index].driving_time().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Returns how the runs are aligned and the `fahrt_weg` of the common start in the reference and the compared run.
+///
+/// Throws [AnalyseError::ZeroDistance] if both runs contain kilometre posts but the start of neither run is found in the other one.
+fn align(reference: &Track, compared: &Track) -> Result<(Alignment, f32, f32), AnalyseError> {
+    let reference_first = reference.points[0];
+    let compared_first = compared.points[0];
+
+    if reference.has_kilometres() && compared.has_kilometres() {
+        let compared_starts_later = reference.find_kilometre(compared_first.kilometre)
+            .map(|fahrt_weg| (fahrt_weg - reference_first.fahrt_weg, (fahrt_weg, compared_first.fahrt_weg)));
+        let reference_starts_later = compared.find_kilometre(reference_first.kilometre)
+            .map(|fahrt_weg| (fahrt_weg - compared_first.fahrt_weg, (reference_first.fahrt_weg, fahrt_weg)));
+
+        let closest = compared_starts_later.into_iter()
+            .chain(reference_starts_later)
+            .min_by(|(first, _), (second, _)| first.total_cmp(second));
+        let (_, (reference_start, compared_start)) = closest.ok_or(AnalyseError::ZeroDistance)?;
+        return Ok((Alignment::Kilometre, reference_start, compared_start));
+    }
+
+    Ok((Alignment::Distance, reference_first.fahrt_weg, compared_first.fahrt_weg))
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+struct Point {
+    fahrt_weg: f32,
+    /// Seconds since the first sample.
+    seconds: f64,
+    kilometre: f32,
+}
+
+/// The measurement samples of a run reduced to what is needed for aligning it.
+struct Track {
+    points: Vec<Point>,
+}
+
+impl Track {
+    fn new(samples: &Samples) -> Result<Track, AnalyseError> {
+        let start = samples.first().ok_or(AnalyseError::NoEntries)?.fahrt_zeit;
+        Ok(Self {
+            points: samples.iter()
+                .map(|sample| Point {
+                    fahrt_weg: sample.fahrt_weg,
+                    seconds: (sample.fahrt_zeit - start).as_seconds_f64(),
+                    kilometre: sample.fahrt_km,
+                })
+                .collect(),
+        })
+    }
+
+    fn end(&self) -> f32 {
+        self.points.last().unwrap().fahrt_weg
+    }
+
+    /// Zusi writes 0 if no kilometre posts are known.
+    fn has_kilometres(&self) -> bool {
+        self.points.iter().any(|point| point.kilometre != 0.)
+    }
+
+    /// Finds the first position at which the kilometre posts pass `kilometre`, ignoring jumps between two samples.
+    fn find_kilometre(&self, kilometre: f32) -> Option<f32> {
+        self.points.windows(2).find_map(|pair| {
+            let (current, next) = (pair[0], pair[1]);
+            let passed = current.kilometre.min(next.kilometre) <= kilometre && kilometre <= current.kilometre.max(next.kilometre);
+            if !passed || !is_continuous(current, next) {
+                return None;
+            }
+
+            let change = next.kilometre - current.kilometre;
+            let fraction = if change == 0. { 0. } else { (kilometre - current.kilometre) / change };
+            Some(current.fahrt_weg + fraction * (next.fahrt_weg - current.fahrt_weg))
+        })
+    }
+
+    /// Returns the samples before and after the first time the train reached `fahrt_weg` and the fraction between both.
+    fn locate(&self, fahrt_weg: f32) -> (Point, Point, f32) {
+        let index = self.points.partition_point(|point| point.fahrt_weg < fahrt_weg);
+        if index == 0 {
+            return (self.points[0], self.points[0], 0.);
+        }
+        let Some(next) = self.points.get(index) else {
+            let last = *self.points.last().unwrap();
+            return (last, last, 0.);
+        };
+
+        let current = self.points[index - 1];
+        let fraction = (fahrt_weg - current.fahrt_weg) / (next.fahrt_weg - current.fahrt_weg);
+        (current, *next, fraction)
+    }
+
+    fn seconds_at(&self, fahrt_weg: f32) -> f64 {
+        let (current, next, fraction) = self.locate(fahrt_weg);
+        current.seconds + fraction as f64 * (next.seconds - current.seconds)
+    }
+
+    /// Interpolates the kilometre posts, except at a jump where the value of the previous sample is kept.
+    fn kilometre_at(&self, fahrt_weg: f32) -> f32 {
+        let (current, next, fraction) = self.locate(fahrt_weg);
+        if is_continuous(current, next) {
+            current.kilometre + fraction * (next.kilometre - current.kilometre)
+        } else {
+            current.kilometre
+        }
+    }
+}
+
+/// Checks whether the kilometre posts between two samples change by the distance driven.
+fn is_continuous(current: Point, next: Point) -> bool {
+    let change = (next.kilometre - current.kilometre).abs() * 1000.;
+    (change - (next.fahrt_weg - current.fahrt_weg)).abs() <= KILOMETRE_TOLERANCE
+}
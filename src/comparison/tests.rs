@@ -0,0 +1,185 @@
+use time::Duration;
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+
+use crate::comparison::{Alignment, ComparisonOptions, Track};
+use crate::fixtures::{self, run};
+use crate::result_analyser::{AnalyseError, ResultAnalyser};
+use crate::samples::Samples;
+
+/// A sample without speed limits and with kilometre posts independent of `fahrt_weg`.
+fn sample(fahrt_weg: f32, seconds: i64, fahrt_km: f32) -> ResultValue {
+    let ResultValue::FahrtEintrag(mut fahrt_eintrag) = fixtures::sample(seconds, fahrt_weg, 0., None);
+    fahrt_eintrag.fahrt_km = fahrt_km;
+    ResultValue::FahrtEintrag(fahrt_eintrag)
+}
+
+fn stop(station: &str, fahrt_weg: f32, seconds: i64, fahrt_km: f32) -> ResultValue {
+    let ResultValue::FahrtEintrag(mut fahrt_eintrag) = sample(fahrt_weg, seconds, fahrt_km);
+    fahrt_eintrag.fahrt_typ = 2;
+    fahrt_eintrag.fahrt_text = station.into();
+    ResultValue::FahrtEintrag(fahrt_eintrag)
+}
+
+fn departure(station: &str, seconds: i64) -> ResultValue {
+    let ResultValue::FahrtEintrag(mut fahrt_eintrag) = sample(-1., seconds, -1.);
+    fahrt_eintrag.fahrt_typ = 2;
+    fahrt_eintrag.fahrt_text = station.into();
+    fahrt_eintrag.fahrt_parameter = 1;
+    ResultValue::FahrtEintrag(fahrt_eintrag)
+}
+
+fn reference() -> ResultAnalyser<ZusiResult> {
+    run(vec![
+        sample(0., 0, 10.),
+        sample(1000., 60, 11.),
+        sample(2000., 120, 12.),
+        sample(3000., 180, 13.),
+        sample(4000., 240, 14.),
+    ])
+}
+
+#[test]
+fn test_compare_aligned_by_kilometre() {
+    // starts 1 km later and has a different fahrt_weg offset
+    let compared = run(vec![
+        sample(500., 0, 11.),
+        sample(1500., 50, 12.),
+        sample(2500., 100, 13.),
+        sample(3500., 190, 14.),
+    ]);
+
+    let comparison = reference().compare(&compared, &ComparisonOptions::default()).unwrap();
+    assert_eq!(comparison.alignment, Alignment::Kilometre);
+    assert_eq!(comparison.distance, 3000.);
+    assert_eq!(comparison.curve.len(), 301);
+    assert_eq!(comparison.curve[0].delta, Duration::ZERO);
+    assert_eq!(comparison.curve[0].kilometre, 11.);
+    assert_eq!(comparison.curve[50].delta, Duration::seconds(-5));
+    assert_eq!(comparison.curve[150].kilometre, 12.5);
+    assert_eq!(comparison.total_delta(), Duration::seconds(10));
+
+    let differences: Vec<Duration> = comparison.sections.iter().map(|section| section.difference).collect();
+    assert_eq!(differences, vec![Duration::seconds(-10), Duration::seconds(-10), Duration::seconds(30)]);
+    assert_eq!(comparison.sections[2].start_km, 13.);
+    assert_eq!(comparison.sections[2].end_km, 14.);
+
+    let gains = comparison.biggest_gains(1);
+    assert_eq!(gains.len(), 1);
+    assert_eq!(gains[0].start, 0.);
+    let losses = comparison.biggest_losses(5);
+    assert_eq!(losses.len(), 1);
+    assert_eq!(losses[0].start, 2000.);
+}
+
+#[test]
+fn test_compare_reference_starts_later() {
+    let compared = run(vec![
+        sample(0., 0, 9.),
+        sample(1000., 70, 10.),
+        sample(2000., 130, 11.),
+    ]);
+
+    let comparison = reference().compare(&compared, &ComparisonOptions::default()).unwrap();
+    assert_eq!(comparison.alignment, Alignment::Kilometre);
+    assert_eq!(comparison.distance, 1000.);
+    assert_eq!(comparison.total_delta(), Duration::ZERO);
+}
+
+#[test]
+fn test_compare_aligned_by_distance() {
+    let reference = run(vec![
+        sample(100., 0, 0.),
+        sample(1100., 60, 0.),
+    ]);
+    let compared = run(vec![
+        sample(0., 0, 0.),
+        sample(1000., 50, 0.),
+        sample(2000., 100, 0.),
+    ]);
+
+    let comparison = reference.compare(&compared, &ComparisonOptions::default()).unwrap();
+    assert_eq!(comparison.alignment, Alignment::Distance);
+    assert_eq!(comparison.distance, 1000.);
+    assert_eq!(comparison.total_delta(), Duration::seconds(-10));
+    assert_eq!(comparison.sections.len(), 1);
+}
+
+#[test]
+fn test_compare_standstill() {
+    // the time of a position is when the train first reached it
+    let compared = run(vec![
+        sample(0., 0, 10.),
+        sample(2000., 120, 12.),
+        sample(2000., 180, 12.),
+        sample(4000., 300, 14.),
+    ]);
+
+    let comparison = reference().compare(&compared, &ComparisonOptions::default()).unwrap();
+    assert_eq!(comparison.curve[200].delta, Duration::ZERO);
+    assert_eq!(comparison.curve[201].delta, Duration::seconds_f64(60.));
+    assert_eq!(comparison.total_delta(), Duration::seconds(60));
+}
+
+#[test]
+fn test_compare_segments() {
+    let reference = run(vec![
+        stop("A", 0., 0, 0.),
+        departure("A", 30),
+        stop("B", 1000., 90, 1.),
+        departure("B", 120),
+        stop("C", 2000., 180, 2.),
+    ]);
+    let compared = run(vec![
+        stop("A", 0., 0, 0.),
+        departure("A", 20),
+        stop("B", 1000., 100, 1.),
+        departure("B", 130),
+        stop("D", 2000., 200, 2.),
+    ]);
+
+    let comparison = reference.compare(&compared, &ComparisonOptions::default()).unwrap();
+    assert_eq!(comparison.segments.len(), 1);
+    assert_eq!(comparison.segments[0].from, "A");
+    assert_eq!(comparison.segments[0].to, "B");
+    assert_eq!(comparison.segments[0].difference(), Duration::seconds(10));
+}
+
+#[test]
+fn test_compare_errors() {
+    let empty = run(vec![]);
+    let options = ComparisonOptions::default();
+
+    assert_eq!(reference().compare(&empty, &options), Err(AnalyseError::NoEntries));
+    assert_eq!(empty.compare(&reference(), &options), Err(AnalyseError::NoEntries));
+    assert_eq!(reference().compare(&run(vec![sample(0., 0, 14.)]), &options), Err(AnalyseError::ZeroDistance));
+
+    // kilometre posts of another line
+    let other_line = run(vec![sample(0., 0, 100.), sample(1000., 60, 101.)]);
+    assert_eq!(reference().compare(&other_line, &options), Err(AnalyseError::ZeroDistance));
+
+    let invalid = ComparisonOptions { resolution: 0., ..options };
+    assert_eq!(reference().compare(&reference(), &invalid), Err(AnalyseError::InvalidOptions));
+    let invalid = ComparisonOptions { section_length: f32::NAN, ..options };
+    assert_eq!(reference().compare(&reference(), &invalid), Err(AnalyseError::InvalidOptions));
+}
+
+#[test]
+fn test_find_kilometre_skips_jumps() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            sample(0., 0, 5.),
+            sample(1000., 60, 6.),
+            // jump in the kilometre posts
+            sample(1100., 70, 2.1),
+            sample(4000., 240, 5.),
+        ])
+        .build();
+    let track = Track::new(&Samples::new(&result)).unwrap();
+
+    assert_eq!(track.find_kilometre(5.5), Some(500.));
+    assert_eq!(track.find_kilometre(4.), Some(3000.));
+    assert_eq!(track.find_kilometre(7.), None);
+    assert_eq!(track.kilometre_at(1050.), 6.);
+}
@@ -0,0 +1,139 @@
+use bitflags::bitflags;
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+#[cfg(test)]
+mod tests;
+
+const FAHRT_TYP_MEASUREMENT: u32 = 0;
+const FAHRT_TYP_STOP: u32 = 1;
+const FAHRT_TYP_TIMETABLE_STOP: u32 = 2;
+const FAHRT_TYP_DEPARTURE_VIOLATION: u32 = 3;
+const FAHRT_TYP_DRIVING_VIOLATION: u32 = 4;
+const FAHRT_TYP_SIGNAL_PASSED: u32 = 5;
+const FAHRT_TYP_TRAIN_PROTECTION: u32 = 8;
+
+const TIMETABLE_STOP_DEPARTURE: u32 = 1;
+
+bitflags! {
+    /// Decoded `fahrt_parameter` of a [EventKind::DepartureViolation].
+    /// Unknown bits are retained.
+    #[derive(PartialEq, Eq, Debug, Clone, Copy)]
+    pub struct DepartureViolationFlags: u32 {
+        /// The train departed without a departure order.
+        const WITHOUT_ORDER = 1 << 0;
+        /// The train departed before the scheduled departure time.
+        const BEFORE_DEPARTURE_TIME = 1 << 1;
+    }
+}
+
+bitflags! {
+    /// Decoded `fahrt_parameter` of a [EventKind::DrivingViolation].
+    /// Unknown bits are retained.
+    #[derive(PartialEq, Eq, Debug, Clone, Copy)]
+    pub struct DrivingViolationFlags: u32 {
+        /// The train stopped with a strong jerk ("Starker Anhalteruck").
+        const HARD_STOP = 1 << 1;
+        /// The train exceeded the permitted speed.
+        const OVERSPEED = 1 << 2;
+    }
+}
+
+bitflags! {
+    /// Decoded `fahrt_parameter` of a [EventKind::TrainProtection] event.
+    /// An event without any flags marks the end of a previously reported state.
+    /// Unknown bits are retained.
+    #[derive(PartialEq, Eq, Debug, Clone, Copy)]
+    pub struct TrainProtectionFlags: u32 {
+        /// The driver acknowledged a train protection influence.
+        const ACKNOWLEDGEMENT = 1 << 4;
+        /// A train protection influence which has to be acknowledged by the driver.
+        const INFLUENCE = 1 << 5;
+        /// The train is supervised against a restrictive braking curve.
+        const RESTRICTIVE_SUPERVISION = 1 << 6;
+        /// The train protection system applied a forced brake.
+        const FORCED_BRAKE = 1 << 7;
+    }
+}
+
+/// Typed meaning of a [FahrtEintrag] which is not a plain measurement sample.
+#[derive(PartialEq, Debug, Clone)]
+pub enum EventKind<'a> {
+    /// The train came to a halt outside of a timetable stop.
+    Stop,
+    /// The train stopped at a timetable stop.
+    TimetableArrival {
+        station: &'a str,
+    },
+    /// The train departed from a timetable stop.
+    TimetableDeparture {
+        station: &'a str,
+    },
+    /// The train departed against the rules, e.g. before the scheduled departure time.
+    DepartureViolation(DepartureViolationFlags),
+    /// The driver violated a driving rule, e.g. by exceeding the permitted speed.
+    DrivingViolation(DrivingViolationFlags),
+    /// The train passed a signal.
+    SignalPassed {
+        signal: &'a str,
+    },
+    /// The state of the train protection system changed.
+    TrainProtection(TrainProtectionFlags),
+    /// An event with a `fahrt_typ` which is not decoded by this library.
+    Unknown {
+        fahrt_typ: u32,
+        fahrt_parameter: u32,
+    },
+}
+
+impl EventKind<'_> {
+    /// Checks whether the event is a [TimetableArrival](EventKind::TimetableArrival)
+    /// or [TimetableDeparture](EventKind::TimetableDeparture) with a station.
+    /// Zusi also writes timetable rows without a `fahrt_text`, e.g. at the end of a timetable, which do not describe a stop.
+    pub fn is_timetable_stop(&self) -> bool {
+        match self {
+            EventKind::TimetableArrival { station } | EventKind::TimetableDeparture { station } => !station.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+/// An event decoded from a single [FahrtEintrag].
+#[derive(PartialEq, Debug, Clone)]
+pub struct Event<'a> {
+    pub fahrt_eintrag: &'a FahrtEintrag,
+    pub kind: EventKind<'a>,
+}
+
+impl<'a> Event<'a> {
+    /// Decodes the `fahrt_typ` and `fahrt_parameter` attributes of a [FahrtEintrag].
+    ///
+    /// Returns [None] for plain measurement samples.
+    pub fn from_fahrt_eintrag(fahrt_eintrag: &'a FahrtEintrag) -> Option<Event<'a>> {
+        let parameter = fahrt_eintrag.fahrt_parameter;
+        let kind = match fahrt_eintrag.fahrt_typ {
+            FAHRT_TYP_MEASUREMENT => return None,
+            FAHRT_TYP_STOP => EventKind::Stop,
+            FAHRT_TYP_TIMETABLE_STOP if parameter == TIMETABLE_STOP_DEPARTURE => EventKind::TimetableDeparture {
+                station: &fahrt_eintrag.fahrt_text,
+            },
+            FAHRT_TYP_TIMETABLE_STOP => EventKind::TimetableArrival {
+                station: &fahrt_eintrag.fahrt_text,
+            },
+            FAHRT_TYP_DEPARTURE_VIOLATION => EventKind::DepartureViolation(DepartureViolationFlags::from_bits_retain(parameter)),
+            FAHRT_TYP_DRIVING_VIOLATION => EventKind::DrivingViolation(DrivingViolationFlags::from_bits_retain(parameter)),
+            FAHRT_TYP_SIGNAL_PASSED => EventKind::SignalPassed {
+                signal: &fahrt_eintrag.fahrt_text,
+            },
+            FAHRT_TYP_TRAIN_PROTECTION => EventKind::TrainProtection(TrainProtectionFlags::from_bits_retain(parameter)),
+            fahrt_typ => EventKind::Unknown {
+                fahrt_typ,
+                fahrt_parameter: parameter,
+            },
+        };
+
+        Some(Self {
+            fahrt_eintrag,
+            kind,
+        })
+    }
+}
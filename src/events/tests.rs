@@ -0,0 +1,115 @@
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::events::{DepartureViolationFlags, DrivingViolationFlags, Event, EventKind, TrainProtectionFlags};
+use crate::result_analyser::ResultAnalyser;
+
+fn event_eintrag(fahrt_typ: u32, fahrt_parameter: u32, fahrt_text: &str) -> FahrtEintrag {
+    FahrtEintrag::builder()
+        .fahrt_typ(fahrt_typ)
+        .fahrt_weg(-1.)
+        .fahrt_zeit(datetime!(2019-01-01 23:18))
+        .fahrt_speed(-1.)
+        .fahrt_parameter(fahrt_parameter)
+        .fahrt_text(fahrt_text.into())
+        .build()
+}
+
+#[test]
+fn test_measurement_is_no_event() {
+    let fahrt_eintrag = FahrtEintrag::builder()
+        .fahrt_weg(2.33)
+        .fahrt_zeit(datetime!(2019-01-01 23:18))
+        .build();
+
+    assert_eq!(Event::from_fahrt_eintrag(&fahrt_eintrag), None);
+}
+
+#[test]
+fn test_timetable_stop() {
+    let arrival = event_eintrag(2, 0, "Hofgeismar");
+    let departure = event_eintrag(2, 1, "Hofgeismar");
+
+    assert_eq!(
+        Event::from_fahrt_eintrag(&arrival).unwrap().kind,
+        EventKind::TimetableArrival { station: "Hofgeismar" }
+    );
+    assert_eq!(
+        Event::from_fahrt_eintrag(&departure).unwrap().kind,
+        EventKind::TimetableDeparture { station: "Hofgeismar" }
+    );
+}
+
+#[test]
+fn test_is_timetable_stop() {
+    let arrival = event_eintrag(2, 0, "Hofgeismar");
+    let untexted = event_eintrag(2, 0, "");
+    let signal = event_eintrag(5, 0, "Hofgeismar");
+
+    assert!(Event::from_fahrt_eintrag(&arrival).unwrap().kind.is_timetable_stop());
+    assert!(!Event::from_fahrt_eintrag(&untexted).unwrap().kind.is_timetable_stop());
+    assert!(!Event::from_fahrt_eintrag(&signal).unwrap().kind.is_timetable_stop());
+}
+
+#[test]
+fn test_flags() {
+    let departure_violation = event_eintrag(3, 3, "Abfahrt ohne Auftrag oder vor Abfahrtzeit");
+    let driving_violation = event_eintrag(4, 2, "Starker Anhalteruck");
+    let train_protection = event_eintrag(8, 192, "");
+
+    assert_eq!(
+        Event::from_fahrt_eintrag(&departure_violation).unwrap().kind,
+        EventKind::DepartureViolation(DepartureViolationFlags::WITHOUT_ORDER | DepartureViolationFlags::BEFORE_DEPARTURE_TIME)
+    );
+    assert_eq!(
+        Event::from_fahrt_eintrag(&driving_violation).unwrap().kind,
+        EventKind::DrivingViolation(DrivingViolationFlags::HARD_STOP)
+    );
+    assert_eq!(
+        Event::from_fahrt_eintrag(&train_protection).unwrap().kind,
+        EventKind::TrainProtection(TrainProtectionFlags::RESTRICTIVE_SUPERVISION | TrainProtectionFlags::FORCED_BRAKE)
+    );
+}
+
+#[test]
+fn test_unknown_values_are_retained() {
+    let unknown_typ = event_eintrag(6, 0, "");
+    let unknown_bits = event_eintrag(8, 16 | 1024, "");
+
+    assert_eq!(
+        Event::from_fahrt_eintrag(&unknown_typ).unwrap().kind,
+        EventKind::Unknown { fahrt_typ: 6, fahrt_parameter: 0 }
+    );
+    let EventKind::TrainProtection(flags) = Event::from_fahrt_eintrag(&unknown_bits).unwrap().kind else {
+        panic!("expected train protection event");
+    };
+    assert!(flags.contains(TrainProtectionFlags::ACKNOWLEDGEMENT));
+    assert_eq!(flags.bits(), 16 | 1024);
+}
+
+#[test]
+fn test_analyser_events() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2.33)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(event_eintrag(5, 0, "Sbk 410 410")),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(22.43)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(event_eintrag(4, 4, "")),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let kinds: Vec<EventKind> = analyser.events().map(|event| event.kind).collect();
+    assert_eq!(kinds, vec![
+        EventKind::SignalPassed { signal: "Sbk 410 410" },
+        EventKind::DrivingViolation(DrivingViolationFlags::OVERSPEED),
+    ]);
+}
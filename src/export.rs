@@ -0,0 +1,105 @@
+use std::io::{self, Write};
+
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+
+use crate::format::DATETIME_FORMAT;
+use crate::samples::{is_measurement, NO_VALUE, Samples};
+use crate::speed_limit::EffectiveLimit;
+use crate::units::{DistanceUnit, SpeedUnit};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct CsvOptions {
+    /// Adds event-only entries and the columns `fahrt_typ`, `fahrt_parameter` and `fahrt_text`.
+    pub include_events: bool,
+    /// Unit of the speed and all limits.
+    pub speed_unit: SpeedUnit,
+    /// Unit of the `fahrt_weg` column, `fahrt_km` is always given in km.
+    pub distance_unit: DistanceUnit,
+}
+
+/// Writes the entries of a [ZusiResult] as CSV with a header line.
+///
+/// The columns are the time, the seconds elapsed since the first measurement sample, `fahrt_weg`, `fahrt_km`, the speed,
+/// the track, signal and train protection limits and the [EffectiveLimit] with its source.
+/// Values which are not set, e.g. inactive limits or the measurement values of event-only entries, are left empty.
+pub fn write_csv<W: Write>(result: &ZusiResult, options: &CsvOptions, mut out: W) -> io::Result<()> {
+    let speed = options.speed_unit.suffix();
+    let distance = options.distance_unit.suffix();
+    write!(
+        out,
+        "fahrt_zeit,elapsed_s,fahrt_weg_{distance},fahrt_km,fahrt_speed_{speed},limit_track_{speed},limit_signal_{speed},\
+        limit_train_protection_{speed},effective_limit_{speed},effective_limit_source",
+    )?;
+    if options.include_events {
+        write!(out, ",fahrt_typ,fahrt_parameter,fahrt_text")?;
+    }
+    writeln!(out)?;
+
+    // event-only entries before the first measurement sample get a negative elapsed time
+    let start = Samples::new(result).first()
+        .map(|fahrt_eintrag| fahrt_eintrag.fahrt_zeit)
+        .or_else(|| result.value.first().map(|value| {
+            let ResultValue::FahrtEintrag(fahrt_eintrag) = value;
+            fahrt_eintrag.fahrt_zeit
+        }));
+
+    for value in result.value.iter() {
+        let ResultValue::FahrtEintrag(fahrt_eintrag) = value;
+        let measurement = is_measurement(fahrt_eintrag);
+        if !measurement && !options.include_events {
+            continue;
+        }
+
+        let effective_limit = EffectiveLimit::from_fahrt_eintrag(fahrt_eintrag);
+        let mut cells = vec![
+            fahrt_eintrag.fahrt_zeit.format(DATETIME_FORMAT).unwrap(),
+            // start is set as there is at least this entry
+            (fahrt_eintrag.fahrt_zeit - start.unwrap()).whole_seconds().to_string(),
+            value_cell(measurement, options.distance_unit.convert(fahrt_eintrag.fahrt_weg)),
+            value_cell(measurement, fahrt_eintrag.fahrt_km),
+            value_cell(measurement, options.speed_unit.convert(fahrt_eintrag.fahrt_speed)),
+            limit_cell(fahrt_eintrag.fahrt_speed_strecke, options.speed_unit),
+            limit_cell(fahrt_eintrag.fahrt_speed_signal, options.speed_unit),
+            limit_cell(fahrt_eintrag.fahrt_speed_zugsicherung, options.speed_unit),
+            effective_limit.map(|limit| options.speed_unit.convert(limit.speed).to_string()).unwrap_or_default(),
+            effective_limit.map(|limit| limit.source.name().to_string()).unwrap_or_default(),
+        ];
+        if options.include_events {
+            cells.push(fahrt_eintrag.fahrt_typ.to_string());
+            cells.push(fahrt_eintrag.fahrt_parameter.to_string());
+            cells.push(escape(&fahrt_eintrag.fahrt_text));
+        }
+
+        writeln!(out, "{}", cells.join(","))?;
+    }
+
+    Ok(())
+}
+
+fn value_cell(measurement: bool, value: f32) -> String {
+    if measurement {
+        value.to_string()
+    } else {
+        String::new()
+    }
+}
+
+fn limit_cell(limit: f32, unit: SpeedUnit) -> String {
+    if limit == NO_VALUE {
+        String::new()
+    } else {
+        unit.convert(limit).to_string()
+    }
+}
+
+/// Quotes a text if it contains a separator, a quote or a line break.
+fn escape(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.into()
+    }
+}
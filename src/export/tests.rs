@@ -0,0 +1,84 @@
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::export::{CsvOptions, write_csv};
+use crate::units::{DistanceUnit, SpeedUnit};
+
+fn result() -> ZusiResult {
+    ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(5)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 22:59:50))
+                .fahrt_speed_strecke(-1.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .fahrt_text("A \"1\", Gleis 2".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_km(12.5)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(0.)
+                .fahrt_speed_strecke(25.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(20.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1500.)
+                .fahrt_km(14.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01:30))
+                .fahrt_speed(20.)
+                .fahrt_speed_strecke(25.)
+                .fahrt_speed_signal(-1.)
+                .fahrt_speed_zugsicherung(-1.)
+                .build()),
+        ])
+        .build()
+}
+
+fn csv(options: &CsvOptions) -> String {
+    let mut out = vec![];
+    write_csv(&result(), options, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_write_csv() {
+    assert_eq!(csv(&CsvOptions::default()), "\
+fahrt_zeit,elapsed_s,fahrt_weg_m,fahrt_km,fahrt_speed_ms,limit_track_ms,limit_signal_ms,limit_train_protection_ms,effective_limit_ms,effective_limit_source
+2019-01-01 23:00:00,0,0,12.5,0,25,,20,20,train_protection
+2019-01-01 23:01:30,90,1500,14,20,25,,,25,track
+");
+}
+
+#[test]
+fn test_write_csv_with_events_and_units() {
+    let options = CsvOptions {
+        include_events: true,
+        speed_unit: SpeedUnit::KilometresPerHour,
+        distance_unit: DistanceUnit::Kilometres,
+    };
+
+    assert_eq!(csv(&options), "\
+fahrt_zeit,elapsed_s,fahrt_weg_km,fahrt_km,fahrt_speed_kmh,limit_track_kmh,limit_signal_kmh,limit_train_protection_kmh,effective_limit_kmh,effective_limit_source,fahrt_typ,fahrt_parameter,fahrt_text
+2019-01-01 22:59:50,-10,,,,,,,,,5,0,\"A \"\"1\"\", Gleis 2\"
+2019-01-01 23:00:00,0,0,12.5,0,90,,72,72,train_protection,0,0,
+2019-01-01 23:01:30,90,1.5,14,72,90,,,90,track,0,0,
+");
+}
+
+#[test]
+fn test_write_csv_without_entries() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let mut out = vec![];
+    write_csv(&result, &CsvOptions::default(), &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap().lines().count(), 1);
+}
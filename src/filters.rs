@@ -0,0 +1,29 @@
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+use crate::events::EventKind;
+use crate::result_analyser::ResultAnalyser;
+
+#[cfg(test)]
+mod tests;
+
+/// Selects runs whose `datum` attribute lies within `start..end`.
+pub fn date_range<R: AsRef<ZusiResult>>(start: PrimitiveDateTime, end: PrimitiveDateTime) -> impl Fn(&ResultAnalyser<R>) -> bool {
+    move |analyser| (start..end).contains(&analyser.result().as_ref().datum)
+}
+
+/// Selects runs with a [distance](ResultAnalyser::distance) of at least `distance` m.
+/// Runs whose distance can't be computed are not selected.
+pub fn min_distance<R: AsRef<ZusiResult>>(distance: f32) -> impl Fn(&ResultAnalyser<R>) -> bool {
+    move |analyser| analyser.distance().is_ok_and(|d| d >= distance)
+}
+
+/// Selects runs with the given `zugnummer` attribute.
+pub fn train_number<R: AsRef<ZusiResult>>(zugnummer: &str) -> impl Fn(&ResultAnalyser<R>) -> bool + '_ {
+    move |analyser| analyser.result().as_ref().zugnummer == zugnummer
+}
+
+/// Selects runs which stopped at the given timetable station.
+pub fn visits_station<R: AsRef<ZusiResult>>(station: &str) -> impl Fn(&ResultAnalyser<R>) -> bool + '_ {
+    move |analyser| analyser.events().any(|event| matches!(event.kind, EventKind::TimetableArrival { station: s } if s == station))
+}
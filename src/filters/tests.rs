@@ -0,0 +1,67 @@
+use time::macros::datetime;
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::filters::{date_range, min_distance, train_number, visits_station};
+use crate::result_analyser::ResultAnalyser;
+use crate::result_analyser_group::{CreateAnalyserGroupError, ResultAnalyserGroup};
+
+fn result(zugnummer: &str, datum: PrimitiveDateTime, station: &str, fahrt_weg: f32) -> ZusiResult {
+    ZusiResult::builder()
+        .zugnummer(zugnummer.into())
+        .datum(datum)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(0.)
+                .fahrt_zeit(datum)
+                .fahrt_text(station.into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(fahrt_weg)
+                .fahrt_zeit(datum)
+                .build()),
+        ])
+        .build()
+}
+
+fn analyser_group() -> ResultAnalyserGroup<ResultAnalyser<ZusiResult>, ZusiResult> {
+    ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result("2083", datetime!(2024-03-23 22:23), "Hümme", 10.)),
+        ResultAnalyser::new(result("2083", datetime!(2024-04-02 8:00), "Hofgeismar", 20.)),
+        ResultAnalyser::new(result("24523", datetime!(2024-03-01 7:00), "Hümme", 30.)),
+    ]).unwrap()
+}
+
+#[test]
+fn test_filter() {
+    let analyser_group = analyser_group();
+
+    assert_eq!(analyser_group.filter(train_number("2083")).unwrap().total_distance().unwrap(), 30.);
+    assert_eq!(analyser_group.filter(min_distance(20.)).unwrap().total_distance().unwrap(), 50.);
+    assert_eq!(analyser_group.filter(visits_station("Hümme")).unwrap().total_distance().unwrap(), 40.);
+    assert_eq!(
+        analyser_group.filter(date_range(datetime!(2024-03-01 0:00), datetime!(2024-04-01 0:00))).unwrap().total_distance().unwrap(),
+        40.
+    );
+}
+
+#[test]
+fn test_filter_results() {
+    let analyser_group = analyser_group();
+    let sub_group = analyser_group.filter_results(|result| result.zugnummer.starts_with('2')).unwrap();
+
+    assert_eq!(sub_group.analysers().len(), 3);
+    assert_eq!(sub_group.average_distance().unwrap(), 20.);
+}
+
+#[test]
+fn test_filter_empty() {
+    let analyser_group = analyser_group();
+
+    assert_eq!(
+        analyser_group.filter(train_number("1")).err(),
+        Some(CreateAnalyserGroupError::NoAnalysers)
+    );
+}
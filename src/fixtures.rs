@@ -0,0 +1,40 @@
+use time::Duration;
+use time::macros::datetime;
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::result_analyser::ResultAnalyser;
+use crate::samples::NO_VALUE;
+use crate::speed_limit::LimitSource;
+
+/// Time of the first sample of a run built by [run].
+pub(crate) const START: PrimitiveDateTime = datetime!(2019-01-01 23:00);
+
+/// A measurement sample `second` seconds after [START], its `fahrt_km` follows `fahrt_weg`.
+/// Only the limit of the given source is set (in m/s), all other limits are [NO_VALUE].
+pub(crate) fn sample(second: i64, fahrt_weg: f32, fahrt_speed: f32, limit: Option<(LimitSource, f32)>) -> ResultValue {
+    let limit_of = |source| match limit {
+        Some((limit_source, limit)) if limit_source == source => limit,
+        _ => NO_VALUE,
+    };
+
+    ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_weg(fahrt_weg)
+        .fahrt_zeit(START + Duration::seconds(second))
+        .fahrt_speed(fahrt_speed)
+        .fahrt_speed_strecke(limit_of(LimitSource::Track))
+        .fahrt_speed_signal(limit_of(LimitSource::Signal))
+        .fahrt_speed_zugsicherung(limit_of(LimitSource::TrainProtection))
+        .fahrt_km(fahrt_weg / 1000.)
+        .build())
+}
+
+/// A run of the given entries with an energy consumption of 1 kWh.
+pub(crate) fn run(values: Vec<ResultValue>) -> ResultAnalyser<ZusiResult> {
+    ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(3_600_000.)
+        .value(values)
+        .build())
+}
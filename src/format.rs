@@ -0,0 +1,5 @@
+use time::format_description::FormatItem;
+use time::macros::format_description;
+
+/// Format of the timestamps in all exports and reports, e.g. `2019-01-01 23:14:00`.
+pub const DATETIME_FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
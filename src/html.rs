@@ -0,0 +1,410 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+use crate::chart::{escape, ChartOptions};
+use crate::format::DATETIME_FORMAT;
+use crate::partition::route;
+use crate::report::{GroupReport, PunctualityReport, ReportOptions, RunReport, SourceFile, StandstillReport};
+use crate::result_analyser::{AnalyseError, ResultAnalyser};
+use crate::result_analyser_group::ResultAnalyserGroup;
+use crate::units::DistanceUnit;
+
+#[cfg(test)]
+mod tests;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222222; }
+h2 { margin-top: 1.5em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #cccccc; padding: 0.2em 0.6em; text-align: left; }
+thead th { background: #f0f0f0; }
+svg { max-width: 100%; height: auto; }";
+
+/// Printed for metrics which could not be calculated and values which are not set.
+const MISSING: &str = "-";
+
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct HtmlOptions {
+    pub report: ReportOptions,
+    /// Size of the chart and the units used in the chart and all tables.
+    pub chart: ChartOptions,
+}
+
+/// Writes a self-contained HTML page for a single run.
+///
+/// The page contains the metadata of the run, all metrics of its [RunReport], the timetable stops,
+/// the periods above the effective speed limit and the [speed chart](crate::chart::speed_chart) as inline SVG.
+/// It does not reference any external stylesheets, scripts or images.
+pub fn write_run_html<R, W>(analyser: &ResultAnalyser<R>, options: &HtmlOptions, mut out: W) -> io::Result<()>
+where
+    R: AsRef<ZusiResult> + SourceFile,
+    W: Write,
+{
+    let formatter = Formatter(&options.chart);
+    let report = RunReport::new(analyser, &options.report);
+    let route = route(analyser);
+
+    write_header(&mut out, &format!("Train {} on {}", report.train_number, formatter.datetime(report.date)))?;
+
+    writeln!(out, "<h2>Metadata</h2>")?;
+    write_key_values(&mut out, &[
+        ("Source file".into(), report.source_file.as_deref().map(|path| path.display().to_string()).unwrap_or(MISSING.into())),
+        ("Train number".into(), report.train_number.clone()),
+        ("Driver number".into(), report.driver_number.clone()),
+        ("Date".into(), formatter.datetime(report.date)),
+        ("From".into(), route.as_ref().map(|route| route.from.clone()).unwrap_or(MISSING.into())),
+        ("To".into(), route.as_ref().map(|route| route.to.clone()).unwrap_or(MISSING.into())),
+    ])?;
+
+    writeln!(out, "<h2>Metrics</h2>")?;
+    write_key_values(&mut out, &run_metrics(&report, &formatter))?;
+
+    writeln!(out, "<h2>Stops</h2>")?;
+    write_table(&mut out, &stop_headers(false), &stop_rows(analyser, options, &formatter, None))?;
+
+    writeln!(out, "<h2>Overspeed</h2>")?;
+    write_table(&mut out, &overspeed_headers(&formatter, false), &overspeed_rows(analyser, options, &formatter, None))?;
+
+    writeln!(out, "<h2>Speed over distance</h2>")?;
+    write_chart(&mut out, analyser.speed_chart(&options.chart))?;
+
+    write_footer(&mut out)
+}
+
+/// Writes a self-contained HTML page for a group of runs.
+///
+/// Like [write_run_html] the page contains the metadata, the metrics of the [GroupReport] and of every run,
+/// the timetable stops and the periods above the effective speed limit of all runs.
+/// The [overlay chart](crate::chart::overlay_chart) is only meaningful if all runs share the same route.
+pub fn write_group_html<A, R, W>(group: &ResultAnalyserGroup<A, R>, options: &HtmlOptions, mut out: W) -> io::Result<()>
+where
+    A: AsRef<ResultAnalyser<R>>,
+    R: AsRef<ZusiResult> + SourceFile,
+    W: Write,
+{
+    let formatter = Formatter(&options.chart);
+    let report = GroupReport::new(group, &options.report);
+    let analysers = group.analysers();
+
+    write_header(&mut out, &format!("{} runs", analysers.len()))?;
+
+    writeln!(out, "<h2>Runs</h2>")?;
+    let runs: Vec<Vec<String>> = analysers.iter().zip(report.runs.iter())
+        .map(|(analyser, run)| {
+            let route = route(analyser.as_ref());
+            vec![
+                run_name(run, &formatter),
+                run.train_number.clone(),
+                run.driver_number.clone(),
+                formatter.datetime(run.date),
+                route.as_ref().map(|route| route.from.clone()).unwrap_or(MISSING.into()),
+                route.as_ref().map(|route| route.to.clone()).unwrap_or(MISSING.into()),
+            ]
+        })
+        .collect();
+    write_table(&mut out, &["Run", "Train number", "Driver number", "Date", "From", "To"].map(String::from), &runs)?;
+
+    writeln!(out, "<h2>Metrics</h2>")?;
+    write_key_values(&mut out, &group_metrics(&report, &formatter))?;
+
+    writeln!(out, "<h2>Run metrics</h2>")?;
+    let headers = [
+        "Run".into(),
+        format!("Distance [{}]", formatter.distance_unit()),
+        "Driving time".into(),
+        "Pure driving time".into(),
+        format!("Average speed [{}]", formatter.speed_unit()),
+        format!("Pure average speed [{}]", formatter.speed_unit()),
+        "Energy consumption [kWh]".into(),
+        "Overspeed intervals".into(),
+        "Punctual share".into(),
+    ];
+    let rows: Vec<Vec<String>> = report.runs.iter()
+        .map(|run| vec![
+            run_name(run, &formatter),
+            value(&run.distance, |distance| formatter.distance(*distance)),
+            value(&run.driving_time, |duration| formatter.duration(*duration)),
+            value(&run.pure_driving_time, |duration| formatter.duration(*duration)),
+            value(&run.average_speed, |speed| formatter.speed(*speed)),
+            value(&run.pure_average_speed, |speed| formatter.speed(*speed)),
+            format!("{:.1}", run.energy_consumption),
+            value(&run.speed_limit, |speed_limit| speed_limit.overspeed_intervals.to_string()),
+            value(&run.punctuality, |punctuality| optional(punctuality.punctual_share, percentage)),
+        ])
+        .collect();
+    write_table(&mut out, &headers, &rows)?;
+
+    writeln!(out, "<h2>Stops</h2>")?;
+    let rows: Vec<Vec<String>> = analysers.iter().zip(report.runs.iter())
+        .flat_map(|(analyser, run)| stop_rows(analyser.as_ref(), options, &formatter, Some(run_name(run, &formatter))))
+        .collect();
+    write_table(&mut out, &stop_headers(true), &rows)?;
+
+    writeln!(out, "<h2>Overspeed</h2>")?;
+    let rows: Vec<Vec<String>> = analysers.iter().zip(report.runs.iter())
+        .flat_map(|(analyser, run)| overspeed_rows(analyser.as_ref(), options, &formatter, Some(run_name(run, &formatter))))
+        .collect();
+    write_table(&mut out, &overspeed_headers(&formatter, true), &rows)?;
+
+    writeln!(out, "<h2>Speed over distance</h2>")?;
+    write_chart(&mut out, group.speed_chart(&options.chart))?;
+
+    write_footer(&mut out)
+}
+
+/// Formats values given in SI units in the units of the chart.
+struct Formatter<'a>(&'a ChartOptions);
+
+impl Formatter<'_> {
+    fn speed_unit(&self) -> &'static str {
+        self.0.speed_unit.symbol()
+    }
+
+    fn distance_unit(&self) -> &'static str {
+        self.0.distance_unit.symbol()
+    }
+
+    fn speed(&self, speed: f32) -> String {
+        format!("{:.1}", self.0.speed_unit.convert(speed))
+    }
+
+    fn distance(&self, distance: f32) -> String {
+        let distance = self.0.distance_unit.convert(distance);
+        match self.0.distance_unit {
+            DistanceUnit::Metres => format!("{distance:.0}"),
+            DistanceUnit::Kilometres => format!("{distance:.3}"),
+        }
+    }
+
+    /// Formats seconds as `h:mm:ss`.
+    fn duration(&self, seconds: f64) -> String {
+        let sign = if seconds < 0. { "-" } else { "" };
+        let seconds = seconds.abs().round() as u64;
+        format!("{sign}{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    }
+
+    fn datetime(&self, datetime: PrimitiveDateTime) -> String {
+        datetime.format(DATETIME_FORMAT).unwrap()
+    }
+}
+
+fn value<T, F: FnOnce(&T) -> String>(value: &Result<T, AnalyseError>, format: F) -> String {
+    value.as_ref().map(format).unwrap_or(MISSING.into())
+}
+
+fn optional<T, F: FnOnce(T) -> String>(value: Option<T>, format: F) -> String {
+    value.map(format).unwrap_or(MISSING.into())
+}
+
+fn percentage(share: f32) -> String {
+    format!("{:.1} %", share * 100.)
+}
+
+/// Name of a run in the tables of a group, the name of its file if known.
+fn run_name(run: &RunReport, formatter: &Formatter) -> String {
+    run.source_file.as_deref()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| format!("{} {}", run.train_number, formatter.datetime(run.date)))
+}
+
+fn run_metrics(report: &RunReport, formatter: &Formatter) -> Vec<(String, String)> {
+    let speed_unit = formatter.speed_unit();
+    let mut metrics = vec![
+        (format!("Distance [{}]", formatter.distance_unit()), value(&report.distance, |distance| formatter.distance(*distance))),
+        ("Driving time".into(), value(&report.driving_time, |duration| formatter.duration(*duration))),
+        ("Pure driving time".into(), value(&report.pure_driving_time, |duration| formatter.duration(*duration))),
+        (format!("Average speed [{speed_unit}]"), value(&report.average_speed, |speed| formatter.speed(*speed))),
+        (format!("Pure average speed [{speed_unit}]"), value(&report.pure_average_speed, |speed| formatter.speed(*speed))),
+        ("Energy consumption [kWh]".into(), format!("{:.1}", report.energy_consumption)),
+        ("Energy consumption per km [kWh/km]".into(), value(&report.energy_consumption_per_km, |energy| format!("{energy:.2}"))),
+        ("Energy consumption per hour [kWh/h]".into(), value(&report.energy_consumption_per_hour, |energy| format!("{energy:.1}"))),
+    ];
+    metrics.extend(standstill_metrics(&report.standstills, formatter));
+    metrics.extend([
+        ("Time above speed limit".into(), value(&report.speed_limit, |speed_limit| formatter.duration(speed_limit.time_above_limit))),
+        (format!("Distance above speed limit [{}]", formatter.distance_unit()),
+         value(&report.speed_limit, |speed_limit| formatter.distance(speed_limit.distance_above_limit))),
+        (format!("Peak overspeed [{speed_unit}]"), value(&report.speed_limit, |speed_limit| formatter.speed(speed_limit.peak_overspeed))),
+        ("Overspeed intervals".into(), value(&report.speed_limit, |speed_limit| speed_limit.overspeed_intervals.to_string())),
+    ]);
+    metrics.extend(punctuality_metrics(&report.punctuality, formatter));
+    metrics.extend([
+        ("Max acceleration [m/s²]".into(), value(&report.kinematics, |kinematics| format!("{:.2}", kinematics.max_acceleration))),
+        ("Mean acceleration [m/s²]".into(), value(&report.kinematics, |kinematics| format!("{:.2}", kinematics.mean_acceleration))),
+        ("Max deceleration [m/s²]".into(), value(&report.kinematics, |kinematics| format!("{:.2}", kinematics.max_deceleration))),
+        ("Mean deceleration [m/s²]".into(), value(&report.kinematics, |kinematics| format!("{:.2}", kinematics.mean_deceleration))),
+        ("Time above comfort acceleration".into(),
+         value(&report.kinematics, |kinematics| formatter.duration(kinematics.time_above_comfort_acceleration))),
+        ("Time above comfort deceleration".into(),
+         value(&report.kinematics, |kinematics| formatter.duration(kinematics.time_above_comfort_deceleration))),
+        ("Braking events".into(), value(&report.kinematics, |kinematics| kinematics.braking_events.to_string())),
+        ("RMS jerk [m/s³]".into(), value(&report.kinematics, |kinematics| format!("{:.2}", kinematics.rms_jerk))),
+        ("Max jerk [m/s³]".into(), value(&report.kinematics, |kinematics| format!("{:.2}", kinematics.max_jerk))),
+    ]);
+    metrics
+}
+
+fn group_metrics(report: &GroupReport, formatter: &Formatter) -> Vec<(String, String)> {
+    let speed_unit = formatter.speed_unit();
+    let distance_unit = formatter.distance_unit();
+    let mut metrics = vec![
+        ("Runs".into(), report.runs.len().to_string()),
+        (format!("Total distance [{distance_unit}]"), value(&report.total_distance, |distance| formatter.distance(*distance))),
+        (format!("Average distance [{distance_unit}]"), value(&report.average_distance, |distance| formatter.distance(*distance))),
+        ("Total driving time".into(), value(&report.total_driving_time, |duration| formatter.duration(*duration))),
+        ("Total pure driving time".into(), value(&report.total_pure_driving_time, |duration| formatter.duration(*duration))),
+        (format!("Average speed [{speed_unit}]"), value(&report.average_speed, |speed| formatter.speed(*speed))),
+        (format!("Pure average speed [{speed_unit}]"), value(&report.pure_average_speed, |speed| formatter.speed(*speed))),
+        ("Total energy consumption [kWh]".into(), format!("{:.1}", report.total_energy_consumption)),
+        ("Average energy consumption per km [kWh/km]".into(),
+         value(&report.average_energy_consumption_per_km, |energy| format!("{energy:.2}"))),
+    ];
+    metrics.extend(standstill_metrics(&report.standstills, formatter));
+    metrics.extend(punctuality_metrics(&report.punctuality, formatter));
+    metrics
+}
+
+fn standstill_metrics(standstills: &Result<StandstillReport, AnalyseError>, formatter: &Formatter) -> [(String, String); 4] {
+    [
+        ("Scheduled stops".into(), value(standstills, |standstills| standstills.scheduled_stops.to_string())),
+        ("Scheduled dwell time".into(), value(standstills, |standstills| formatter.duration(standstills.scheduled_dwell_time))),
+        ("Unscheduled stops".into(), value(standstills, |standstills| standstills.unscheduled_stops.to_string())),
+        ("Unscheduled stop time".into(), value(standstills, |standstills| formatter.duration(standstills.unscheduled_stop_time))),
+    ]
+}
+
+fn punctuality_metrics(punctuality: &Result<PunctualityReport, AnalyseError>, formatter: &Formatter) -> [(String, String); 6] {
+    [
+        ("Timetable stops".into(), value(punctuality, |punctuality| punctuality.stops.to_string())),
+        ("Punctual stops".into(), value(punctuality, |punctuality| punctuality.punctual_stops.to_string())),
+        ("Punctual share".into(), value(punctuality, |punctuality| optional(punctuality.punctual_share, percentage))),
+        ("Average arrival delay".into(),
+         value(punctuality, |punctuality| optional(punctuality.average_arrival_delay, |delay| formatter.duration(delay)))),
+        ("Average departure delay".into(),
+         value(punctuality, |punctuality| optional(punctuality.average_departure_delay, |delay| formatter.duration(delay)))),
+        ("Max delay".into(), value(punctuality, |punctuality| optional(punctuality.max_delay, |delay| formatter.duration(delay)))),
+    ]
+}
+
+fn stop_headers(with_run: bool) -> Vec<String> {
+    with_run.then(|| "Run".to_string()).into_iter()
+        .chain([
+            "Station",
+            "Scheduled arrival",
+            "Arrival",
+            "Arrival delay",
+            "Scheduled departure",
+            "Departure",
+            "Departure delay",
+        ].map(String::from))
+        .collect()
+}
+
+/// One row per timetable stop, prefixed with the name of the run if given.
+fn stop_rows<R: AsRef<ZusiResult>>(
+    analyser: &ResultAnalyser<R>, options: &HtmlOptions, formatter: &Formatter, run: Option<String>,
+) -> Vec<Vec<String>> {
+    let Ok(punctuality) = analyser.punctuality(options.report.punctuality_threshold) else {
+        return vec![];
+    };
+
+    punctuality.stops.iter()
+        .map(|stop| run.iter().cloned()
+            .chain([
+                stop.station.clone(),
+                optional(stop.scheduled_arrival, |datetime| formatter.datetime(datetime)),
+                optional(stop.actual_arrival, |datetime| formatter.datetime(datetime)),
+                optional(stop.arrival_delay(), |delay| formatter.duration(delay.as_seconds_f64())),
+                optional(stop.scheduled_departure, |datetime| formatter.datetime(datetime)),
+                optional(stop.actual_departure, |datetime| formatter.datetime(datetime)),
+                optional(stop.departure_delay(), |delay| formatter.duration(delay.as_seconds_f64())),
+            ])
+            .collect())
+        .collect()
+}
+
+fn overspeed_headers(formatter: &Formatter, with_run: bool) -> Vec<String> {
+    with_run.then(|| "Run".to_string()).into_iter()
+        .chain([
+            "Start".into(),
+            "End".into(),
+            "Duration".into(),
+            "Start km".into(),
+            "End km".into(),
+            format!("Peak overspeed [{}]", formatter.speed_unit()),
+            "Limited by".into(),
+        ])
+        .collect()
+}
+
+/// One row per period above the effective speed limit, prefixed with the name of the run if given.
+fn overspeed_rows<R: AsRef<ZusiResult>>(
+    analyser: &ResultAnalyser<R>, options: &HtmlOptions, formatter: &Formatter, run: Option<String>,
+) -> Vec<Vec<String>> {
+    let Ok(compliance) = analyser.speed_limit_compliance(options.report.speed_limit_tolerance) else {
+        return vec![];
+    };
+
+    compliance.intervals.iter()
+        .map(|interval| run.iter().cloned()
+            .chain([
+                formatter.datetime(interval.start),
+                formatter.datetime(interval.end),
+                formatter.duration(interval.duration().as_seconds_f64()),
+                format!("{:.3}", interval.start_km),
+                format!("{:.3}", interval.end_km),
+                formatter.speed(interval.peak_overspeed),
+                interval.source.name().into(),
+            ])
+            .collect())
+        .collect()
+}
+
+fn write_header<W: Write>(out: &mut W, title: &str) -> io::Result<()> {
+    writeln!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+        <style>\n{STYLE}\n</style>\n</head>\n<body>\n<h1>{title}</h1>",
+        title = escape(title),
+    )
+}
+
+fn write_footer<W: Write>(out: &mut W) -> io::Result<()> {
+    writeln!(out, "</body>\n</html>")
+}
+
+fn write_key_values<W: Write>(out: &mut W, rows: &[(String, String)]) -> io::Result<()> {
+    writeln!(out, "<table>")?;
+    for (key, value) in rows {
+        writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", escape(key), escape(value))?;
+    }
+    writeln!(out, "</table>")
+}
+
+fn write_table<W: Write>(out: &mut W, headers: &[String], rows: &[Vec<String>]) -> io::Result<()> {
+    if rows.is_empty() {
+        return writeln!(out, "<p>None.</p>");
+    }
+
+    writeln!(out, "<table>\n<thead>\n<tr>{}</tr>\n</thead>\n<tbody>", cells("th", headers))?;
+    for row in rows {
+        writeln!(out, "<tr>{}</tr>", cells("td", row))?;
+    }
+    writeln!(out, "</tbody>\n</table>")
+}
+
+fn cells(tag: &str, cells: &[String]) -> String {
+    cells.iter()
+        .map(|cell| format!("<{tag}>{}</{tag}>", escape(cell)))
+        .collect()
+}
+
+fn write_chart<W: Write>(out: &mut W, chart: Result<String, AnalyseError>) -> io::Result<()> {
+    match chart {
+        Ok(svg) => write!(out, "{svg}"),
+        Err(_) => writeln!(out, "<p>No measurement samples.</p>"),
+    }
+}
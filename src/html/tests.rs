@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::chart::ChartOptions;
+use crate::html::{Formatter, HtmlOptions, write_group_html, write_run_html};
+use crate::loader::LoadedResult;
+use crate::result_analyser::ResultAnalyser;
+use crate::result_analyser_group::ResultAnalyserGroup;
+use crate::units::{DistanceUnit, SpeedUnit};
+
+fn result(zugnummer: &str) -> ZusiResult {
+    ZusiResult::builder()
+        .zugnummer(zugnummer.into())
+        .tf_nummer("42".into())
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(3_600_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_speed(20.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01))
+                .build()),
+        ])
+        .build()
+}
+
+fn run_html(analyser: &ResultAnalyser<ZusiResult>) -> String {
+    let mut html = vec![];
+    write_run_html(analyser, &HtmlOptions::default(), &mut html).unwrap();
+    String::from_utf8(html).unwrap()
+}
+
+#[test]
+fn test_run_html() {
+    let html = run_html(&ResultAnalyser::new(result("12345")));
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.ends_with("</html>\n"));
+    assert!(html.contains("<h1>Train 12345 on 2019-01-01 23:14:00</h1>"));
+    assert!(html.contains("<tr><th>Driver number</th><td>42</td></tr>"));
+    assert!(html.contains("<tr><th>Distance [km]</th><td>1.000</td></tr>"));
+    assert!(html.contains("<tr><th>Driving time</th><td>0:01:00</td></tr>"));
+    assert!(html.contains("<tr><th>Timetable stops</th><td>-</td></tr>"));
+    assert!(html.contains("<h2>Stops</h2>\n<p>None.</p>"));
+    assert!(html.contains("<svg"));
+}
+
+#[test]
+fn test_run_html_is_self_contained() {
+    let html = run_html(&ResultAnalyser::new(result("12345")));
+
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("<link"));
+    assert!(!html.contains("src="));
+    assert!(!html.contains("href="));
+}
+
+#[test]
+fn test_run_html_escapes_text() {
+    let html = run_html(&ResultAnalyser::new(result("<b>1 & 2</b>")));
+
+    assert!(html.contains("<title>Train &lt;b&gt;1 &amp; 2&lt;/b&gt; on 2019-01-01 23:14:00</title>"));
+    assert!(!html.contains("<b>"));
+}
+
+#[test]
+fn test_run_html_without_samples() {
+    let html = run_html(&ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build()));
+
+    assert!(html.contains("<tr><th>Distance [km]</th><td>-</td></tr>"));
+    assert!(html.contains("<p>No measurement samples.</p>"));
+}
+
+#[test]
+fn test_group_html() {
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(LoadedResult {
+            path: PathBuf::from("data/first.result.xml"),
+            result: result("1"),
+        }),
+        ResultAnalyser::new(LoadedResult {
+            path: PathBuf::from("data/second.result.xml"),
+            result: result("2"),
+        }),
+    ]).unwrap();
+
+    let mut html = vec![];
+    write_group_html(&analyser_group, &HtmlOptions::default(), &mut html).unwrap();
+    let html = String::from_utf8(html).unwrap();
+
+    assert!(html.contains("<h1>2 runs</h1>"));
+    assert!(html.contains("<tr><th>Total distance [km]</th><td>2.000</td></tr>"));
+    assert!(html.contains("<tr><td>first.result.xml</td><td>1</td><td>42</td>"));
+    assert!(html.contains("<tr><td>second.result.xml</td><td>1.000</td><td>0:01:00</td>"));
+    assert_eq!(html.matches("<svg").count(), 1);
+}
+
+#[test]
+fn test_formatter() {
+    let options = ChartOptions {
+        speed_unit: SpeedUnit::MetresPerSecond,
+        distance_unit: DistanceUnit::Metres,
+        ..Default::default()
+    };
+    let formatter = Formatter(&options);
+
+    assert_eq!(formatter.speed(12.34), "12.3");
+    assert_eq!(formatter.distance(1234.4), "1234");
+    assert_eq!(formatter.duration(3725.), "1:02:05");
+    assert_eq!(formatter.duration(-61.), "-0:01:01");
+}
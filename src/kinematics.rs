@@ -0,0 +1,195 @@
+use time::{Duration, PrimitiveDateTime};
+
+use crate::result_analyser::AnalyseError;
+use crate::samples::Samples;
+
+#[cfg(test)]
+mod tests;
+
+/// Thresholds used by the kinematics analysis, all values in m/s².
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct KinematicsOptions {
+    /// Accelerations above this value count as uncomfortable.
+    pub comfort_acceleration: f32,
+    /// Decelerations above this value count as uncomfortable.
+    pub comfort_deceleration: f32,
+    /// Decelerations above this value count as braking.
+    pub braking_deceleration: f32,
+}
+
+impl Default for KinematicsOptions {
+    fn default() -> Self {
+        Self {
+            comfort_acceleration: 1.,
+            comfort_deceleration: 1.,
+            braking_deceleration: 0.1,
+        }
+    }
+}
+
+/// A period in which the train continuously decelerated by more than [KinematicsOptions::braking_deceleration].
+#[derive(PartialEq, Debug, Clone)]
+pub struct BrakingEvent {
+    pub start: PrimitiveDateTime,
+    pub end: PrimitiveDateTime,
+    /// Speed at the start in m/s.
+    pub start_speed: f32,
+    /// Speed at the end in m/s.
+    pub end_speed: f32,
+}
+
+impl BrakingEvent {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// Computes the mean deceleration in m/s² as a positive value.
+    pub fn mean_deceleration(&self) -> f32 {
+        (self.start_speed - self.end_speed) / self.duration().as_seconds_f32()
+    }
+}
+
+/// Result of a kinematics analysis.
+/// Decelerations are given as positive values.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Kinematics {
+    /// Highest acceleration in m/s².
+    pub max_acceleration: f32,
+    /// Time weighted mean of all accelerating periods in m/s².
+    pub mean_acceleration: f32,
+    /// Highest deceleration in m/s².
+    pub max_deceleration: f32,
+    /// Time weighted mean of all decelerating periods in m/s².
+    pub mean_deceleration: f32,
+    pub time_above_comfort_acceleration: Duration,
+    pub time_above_comfort_deceleration: Duration,
+    pub braking_events: Vec<BrakingEvent>,
+    /// Root mean square of the jerk between consecutive periods in m/s³.
+    pub rms_jerk: f32,
+    /// Highest absolute jerk in m/s³.
+    pub max_jerk: f32,
+}
+
+/// A period between two measurement samples with different `fahrt_zeit`.
+struct Period {
+    start: PrimitiveDateTime,
+    end: PrimitiveDateTime,
+    start_speed: f32,
+    end_speed: f32,
+}
+
+impl Period {
+    fn seconds(&self) -> f32 {
+        (self.end - self.start).as_seconds_f32()
+    }
+
+    fn acceleration(&self) -> f32 {
+        (self.end_speed - self.start_speed) / self.seconds()
+    }
+}
+
+/// Splits the samples into periods of constant acceleration.
+/// Of several samples sharing the same `fahrt_zeit` only the last one is used, so the duration of each period is never zero.
+fn periods(samples: &Samples) -> Vec<Period> {
+    let mut points: Vec<(PrimitiveDateTime, f32)> = vec![];
+    for sample in samples.iter() {
+        match points.last_mut() {
+            Some((fahrt_zeit, fahrt_speed)) if *fahrt_zeit == sample.fahrt_zeit => *fahrt_speed = sample.fahrt_speed,
+            _ => points.push((sample.fahrt_zeit, sample.fahrt_speed)),
+        }
+    }
+
+    points.windows(2)
+        .map(|pair| Period {
+            start: pair[0].0,
+            end: pair[1].0,
+            start_speed: pair[0].1,
+            end_speed: pair[1].1,
+        })
+        .collect()
+}
+
+/// Derives the longitudinal acceleration from the `fahrt_speed` and `fahrt_zeit` attributes of consecutive measurement samples.
+///
+/// Throws [AnalyseError::NoEntries] if there are no measurement samples
+/// and [AnalyseError::ZeroDrivingTime] if all samples share the same `fahrt_zeit`.
+pub fn kinematics(samples: &Samples, options: &KinematicsOptions) -> Result<Kinematics, AnalyseError> {
+    if samples.is_empty() {
+        return Err(AnalyseError::NoEntries);
+    }
+    let periods = periods(samples);
+    if periods.is_empty() {
+        return Err(AnalyseError::ZeroDrivingTime);
+    }
+
+    let mut kinematics = Kinematics {
+        max_acceleration: 0.,
+        mean_acceleration: 0.,
+        max_deceleration: 0.,
+        mean_deceleration: 0.,
+        time_above_comfort_acceleration: Duration::seconds(0),
+        time_above_comfort_deceleration: Duration::seconds(0),
+        braking_events: vec![],
+        rms_jerk: 0.,
+        max_jerk: 0.,
+    };
+    let mut accelerating_seconds = 0.;
+    let mut decelerating_seconds = 0.;
+    let mut current_braking_event: Option<BrakingEvent> = None;
+
+    for period in periods.iter() {
+        let acceleration = period.acceleration();
+        let seconds = period.seconds();
+
+        if acceleration > 0. {
+            kinematics.max_acceleration = kinematics.max_acceleration.max(acceleration);
+            kinematics.mean_acceleration += acceleration * seconds;
+            accelerating_seconds += seconds;
+            if acceleration > options.comfort_acceleration {
+                kinematics.time_above_comfort_acceleration += period.end - period.start;
+            }
+        } else if acceleration < 0. {
+            kinematics.max_deceleration = kinematics.max_deceleration.max(-acceleration);
+            kinematics.mean_deceleration -= acceleration * seconds;
+            decelerating_seconds += seconds;
+            if -acceleration > options.comfort_deceleration {
+                kinematics.time_above_comfort_deceleration += period.end - period.start;
+            }
+        }
+
+        if -acceleration > options.braking_deceleration {
+            match current_braking_event.as_mut() {
+                Some(braking_event) => {
+                    braking_event.end = period.end;
+                    braking_event.end_speed = period.end_speed;
+                }
+                None => current_braking_event = Some(BrakingEvent {
+                    start: period.start,
+                    end: period.end,
+                    start_speed: period.start_speed,
+                    end_speed: period.end_speed,
+                }),
+            }
+        } else {
+            kinematics.braking_events.extend(current_braking_event.take());
+        }
+    }
+    kinematics.braking_events.extend(current_braking_event);
+
+    if accelerating_seconds > 0. {
+        kinematics.mean_acceleration /= accelerating_seconds;
+    }
+    if decelerating_seconds > 0. {
+        kinematics.mean_deceleration /= decelerating_seconds;
+    }
+
+    let jerks: Vec<f32> = periods.windows(2)
+        .map(|pair| (pair[1].acceleration() - pair[0].acceleration()) / ((pair[0].seconds() + pair[1].seconds()) / 2.))
+        .collect();
+    if !jerks.is_empty() {
+        kinematics.rms_jerk = (jerks.iter().map(|jerk| jerk * jerk).sum::<f32>() / jerks.len() as f32).sqrt();
+        kinematics.max_jerk = jerks.iter().fold(0., |max: f32, jerk| max.max(jerk.abs()));
+    }
+
+    Ok(kinematics)
+}
@@ -0,0 +1,54 @@
+use time::Duration;
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::fixtures::{self, run};
+use crate::kinematics::KinematicsOptions;
+use crate::result_analyser::AnalyseError;
+
+fn sample(second: i64, fahrt_speed: f32) -> ResultValue {
+    fixtures::sample(second, 0., fahrt_speed, None)
+}
+
+#[test]
+fn test_kinematics() {
+    let analyser = run(vec![
+        sample(0, 0.),
+        sample(10, 5.),
+        sample(12, 9.),
+        sample(12, 10.),
+        sample(20, 10.),
+        sample(30, 5.),
+        sample(35, 0.),
+    ]);
+    let kinematics = analyser.kinematics(&KinematicsOptions::default()).unwrap();
+
+    assert_eq!(kinematics.max_acceleration, 2.5);
+    assert_eq!(kinematics.mean_acceleration, 10. / 12.);
+    assert_eq!(kinematics.max_deceleration, 1.);
+    assert_eq!(kinematics.mean_deceleration, 10. / 15.);
+    assert_eq!(kinematics.time_above_comfort_acceleration, Duration::seconds(2));
+    assert_eq!(kinematics.time_above_comfort_deceleration, Duration::seconds(0));
+    assert_eq!(kinematics.braking_events.len(), 1);
+    assert_eq!(kinematics.braking_events[0].start, datetime!(2019-01-01 23:00:20));
+    assert_eq!(kinematics.braking_events[0].end_speed, 0.);
+    assert_eq!(kinematics.braking_events[0].duration(), Duration::seconds(15));
+    assert_eq!(kinematics.braking_events[0].mean_deceleration(), 10. / 15.);
+    assert_eq!(kinematics.max_jerk, 0.5);
+    assert!(kinematics.rms_jerk > 0. && kinematics.rms_jerk.is_finite());
+}
+
+#[test]
+fn test_kinematics_identical_fahrt_zeit() {
+    let analyser = run(vec![
+        sample(0, 0.),
+        sample(0, 1.),
+    ]);
+    assert_eq!(analyser.kinematics(&KinematicsOptions::default()), Err(AnalyseError::ZeroDrivingTime));
+}
+
+#[test]
+fn test_kinematics_0() {
+    let analyser = run(vec![]);
+    assert_eq!(analyser.kinematics(&KinematicsOptions::default()), Err(AnalyseError::NoEntries));
+}
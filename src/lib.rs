@@ -4,5 +4,72 @@
 /// Contains everything for analysing a single `.result.xml` file.
 pub mod result_analyser;
 
+/// Contains a view over the measurement samples of a `.result.xml` file.
+pub mod samples;
+
+/// Contains typed events decoded from the `fahrt_typ` and `fahrt_parameter` attributes.
+pub mod events;
+
+/// Contains the acceleration, braking and jerk analysis.
+pub mod kinematics;
+
+/// Contains built-in predicates for filtering a [ResultAnalyserGroup](result_analyser_group::ResultAnalyserGroup).
+pub mod filters;
+
+/// Contains built-in keys for partitioning a [ResultAnalyserGroup](result_analyser_group::ResultAnalyserGroup).
+pub mod partition;
+
+/// Contains the splitting of a run into station-to-station segments.
+pub mod segments;
+
+/// Contains the alignment and comparison of two runs of the same route.
+pub mod comparison;
+
+/// Contains the configurable driver score with its breakdown.
+pub mod score;
+
+/// Contains the speed limit compliance analysis.
+pub mod speed_limit;
+
+/// Contains distribution statistics of per-run metrics.
+pub mod statistics;
+
+/// Contains the timetable punctuality analysis.
+pub mod timetable;
+
+/// Contains the log of passed signals with their approach speeds.
+pub mod signals;
+
+/// Contains the analysis of train protection (PZB/LZB) events.
+pub mod train_protection;
+
+/// Contains the detection of standstills and their attribution to timetable stops.
+pub mod standstill;
+
+/// Contains the loading of `.result.xml` files from files, directories and glob patterns.
+pub mod loader;
+
+/// Contains the formats shared by exports, reports and the command-line tool.
+pub mod format;
+
+/// Contains the units selectable for exports and charts.
+pub mod units;
+
+/// Contains the CSV export of the measurement samples.
+pub mod export;
+
+/// Contains the rendering of speed-over-distance charts as SVG.
+pub mod chart;
+
+/// Contains the self-contained HTML report of a run or group.
+pub mod html;
+
+/// Contains reports collecting all metrics of a run or group, serialisable with the `serde` feature.
+pub mod report;
+
 /// Contains everything for analysing multiple `.result.xml` files by aggregating the single results.
-pub mod result_analyser_group;
\ No newline at end of file
+pub mod result_analyser_group;
+
+/// Contains the shared builders of runs for the unit tests.
+#[cfg(test)]
+pub(crate) mod fixtures;
\ No newline at end of file
@@ -0,0 +1,225 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use zusi_xml_lib::xml::zusi::{Zusi, ZusiValue};
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+#[cfg(test)]
+mod tests;
+
+/// File name suffix of the result files Zusi writes.
+/// Only files with this suffix are loaded from directories.
+pub const RESULT_FILE_SUFFIX: &str = ".result.xml";
+
+/// Zusi writes its xml files with an UTF-8 byte order mark.
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Describes where result files are loaded from.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Source {
+    /// A single file, loaded regardless of its file name.
+    File(PathBuf),
+    /// All files ending with [RESULT_FILE_SUFFIX] in a directory, optionally including all sub-directories.
+    Directory { path: PathBuf, recursive: bool },
+    /// All files matching a glob pattern, e.g. `data/**/*.result.xml`.
+    Glob(String),
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Xml(quick_xml::DeError),
+    Pattern(glob::PatternError),
+    /// The file was parsed successfully but does not contain a `result` element.
+    NoResult,
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(f, "i/o error: {error}"),
+            LoadError::Xml(error) => write!(f, "xml error: {error}"),
+            LoadError::Pattern(error) => write!(f, "invalid glob pattern: {error}"),
+            LoadError::NoResult => write!(f, "no result element found"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// A [ZusiResult] together with the file it was loaded from.
+#[derive(PartialEq, Debug, Clone)]
+pub struct LoadedResult {
+    pub path: PathBuf,
+    pub result: ZusiResult,
+}
+
+impl AsRef<ZusiResult> for LoadedResult {
+    fn as_ref(&self) -> &ZusiResult {
+        &self.result
+    }
+}
+
+/// A file or pattern which could not be loaded.
+#[derive(Debug)]
+pub struct LoadFailure {
+    pub path: PathBuf,
+    pub error: LoadError,
+}
+
+/// All results and failures of a batch.
+/// Both are ordered by the given sources, files within a directory or pattern are sorted by path.
+#[derive(Debug, Default)]
+pub struct Loaded {
+    pub results: Vec<LoadedResult>,
+    pub failures: Vec<LoadFailure>,
+}
+
+/// Loads all result files of the given sources.
+/// A failing file does not abort the batch, it is reported in [Loaded::failures] instead.
+/// With the `parallel` feature the files are parsed in parallel, the order stays the same.
+pub fn load(sources: &[Source]) -> Loaded {
+    let paths = resolve(sources);
+
+    #[cfg(feature = "parallel")]
+    let outcomes: Vec<_> = {
+        use rayon::prelude::*;
+        paths.into_par_iter().map(|path| path.and_then(load_path)).collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let outcomes = paths.into_iter().map(|path| path.and_then(load_path));
+
+    let mut loaded = Loaded::default();
+
+    for outcome in outcomes {
+        match outcome {
+            Ok(results) => loaded.results.extend(results),
+            Err(failure) => loaded.failures.push(failure),
+        }
+    }
+
+    loaded
+}
+
+fn load_path(path: PathBuf) -> Result<Vec<LoadedResult>, LoadFailure> {
+    match load_file(&path) {
+        Ok(results) => Ok(results.into_iter()
+            .map(|result| LoadedResult { path: path.clone(), result })
+            .collect()),
+        Err(error) => Err(LoadFailure { path, error }),
+    }
+}
+
+/// Loads all results of a single file.
+///
+/// Throws [LoadError::Io] if the file can't be read, for all other errors see [parse].
+pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<ZusiResult>, LoadError> {
+    let contents = fs::read_to_string(path).map_err(LoadError::Io)?;
+    parse(&contents)
+}
+
+/// Parses all results of the contents of a `.result.xml` file, a leading byte order mark is skipped.
+///
+/// Throws [LoadError::Xml] if the contents are not valid and [LoadError::NoResult] if they do not contain any result.
+pub fn parse(contents: &str) -> Result<Vec<ZusiResult>, LoadError> {
+    let contents = contents.strip_prefix(BYTE_ORDER_MARK).unwrap_or(contents);
+    let zusi = Zusi::from_xml(contents).map_err(LoadError::Xml)?;
+
+    let results: Vec<ZusiResult> = zusi.value.into_iter()
+        .filter_map(|value| match value {
+            ZusiValue::Result(result) => Some(result),
+            _ => None,
+        })
+        .collect();
+
+    if results.is_empty() {
+        Err(LoadError::NoResult)
+    } else {
+        Ok(results)
+    }
+}
+
+/// Expands all sources to the list of files to load.
+/// Unreadable directories and invalid patterns are kept at their position as failures.
+fn resolve(sources: &[Source]) -> Vec<Result<PathBuf, LoadFailure>> {
+    let mut paths = vec![];
+
+    for source in sources {
+        match source {
+            Source::File(path) => paths.push(Ok(path.clone())),
+            Source::Directory { path, recursive } => {
+                let mut directory_paths = vec![];
+                read_directory(path, *recursive, &mut directory_paths);
+                directory_paths.sort_by(|a, b| sort_key(a).cmp(sort_key(b)));
+                paths.extend(directory_paths);
+            }
+            Source::Glob(pattern) => match glob::glob(pattern) {
+                Ok(entries) => {
+                    let mut pattern_paths = vec![];
+                    for entry in entries {
+                        match entry {
+                            Ok(path) => if path.is_file() {
+                                pattern_paths.push(Ok(path))
+                            },
+                            Err(error) => pattern_paths.push(Err(LoadFailure {
+                                path: error.path().to_path_buf(),
+                                error: LoadError::Io(error.into()),
+                            })),
+                        }
+                    }
+                    pattern_paths.sort_by(|a, b| sort_key(a).cmp(sort_key(b)));
+                    paths.extend(pattern_paths);
+                }
+                Err(error) => paths.push(Err(LoadFailure {
+                    path: PathBuf::from(pattern),
+                    error: LoadError::Pattern(error),
+                })),
+            },
+        }
+    }
+
+    paths
+}
+
+fn read_directory(path: &Path, recursive: bool, paths: &mut Vec<Result<PathBuf, LoadFailure>>) {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(error) => {
+            paths.push(Err(LoadFailure { path: path.to_path_buf(), error: LoadError::Io(error) }));
+            return;
+        }
+    };
+
+    for entry in entries {
+        let entry_path = match entry {
+            Ok(entry) => entry.path(),
+            Err(error) => {
+                paths.push(Err(LoadFailure { path: path.to_path_buf(), error: LoadError::Io(error) }));
+                continue;
+            }
+        };
+
+        if entry_path.is_dir() {
+            if recursive {
+                read_directory(&entry_path, recursive, paths);
+            }
+        } else if is_result_file(&entry_path) {
+            paths.push(Ok(entry_path));
+        }
+    }
+}
+
+fn sort_key(path: &Result<PathBuf, LoadFailure>) -> &Path {
+    match path {
+        Ok(path) => path,
+        Err(failure) => &failure.path,
+    }
+}
+
+fn is_result_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|file_name| file_name.to_str())
+        .is_some_and(|file_name| file_name.ends_with(RESULT_FILE_SUFFIX))
+}
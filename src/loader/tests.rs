@@ -0,0 +1,128 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::loader::{load, parse, LoadError, Source};
+
+const RESULT: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<Zusi>
+<Info DateiTyp=\"result\" Version=\"A.2\" MinVersion=\"A.0\"/>
+<result Zugnummer=\"24523\" Datum=\"2024-03-23 22:23:31\" Verbrauch=\"3600000\">
+<FahrtEintrag FahrtWeg=\"0\" FahrtZeit=\"2018-11-06 07:26:00\">
+</FahrtEintrag>
+<FahrtEintrag FahrtWeg=\"1000\" FahrtZeit=\"2018-11-06 07:27:00\">
+</FahrtEintrag>
+</result>
+</Zusi>
+";
+
+const NO_RESULT: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<Zusi>
+<Info DateiTyp=\"result\" Version=\"A.2\" MinVersion=\"A.0\"/>
+</Zusi>
+";
+
+fn write(directory: &Path, name: &str, contents: &str) -> PathBuf {
+    let path = directory.join(name);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_parse() {
+    let results = parse(RESULT).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].zugnummer, "24523");
+    assert_eq!(results[0].value.len(), 2);
+}
+
+#[test]
+fn test_parse_with_byte_order_mark() {
+    let results = parse(&format!("\u{feff}{RESULT}")).unwrap();
+    assert_eq!(results.len(), 1);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(parse(NO_RESULT), Err(LoadError::NoResult)));
+    assert!(matches!(parse("<Zusi><result"), Err(LoadError::Xml(_))));
+}
+
+#[test]
+fn test_load_directory() {
+    let directory = tempfile::tempdir().unwrap();
+    let b = write(directory.path(), "b.result.xml", RESULT);
+    let a = write(directory.path(), "a.result.xml", RESULT);
+    let nested = write(directory.path(), "nested/c.result.xml", RESULT);
+    write(directory.path(), "notes.txt", "not a result");
+
+    let loaded = load(&[Source::Directory { path: directory.path().to_path_buf(), recursive: false }]);
+    assert!(loaded.failures.is_empty());
+    let paths: Vec<&PathBuf> = loaded.results.iter().map(|result| &result.path).collect();
+    assert_eq!(paths, vec![&a, &b]);
+
+    let loaded = load(&[Source::Directory { path: directory.path().to_path_buf(), recursive: true }]);
+    assert!(loaded.failures.is_empty());
+    let paths: Vec<&PathBuf> = loaded.results.iter().map(|result| &result.path).collect();
+    assert_eq!(paths, vec![&a, &b, &nested]);
+}
+
+#[test]
+fn test_load_glob() {
+    let directory = tempfile::tempdir().unwrap();
+    let a = write(directory.path(), "a.result.xml", RESULT);
+    let nested = write(directory.path(), "nested/b.result.xml", RESULT);
+    write(directory.path(), "nested/notes.txt", "not a result");
+
+    let pattern = format!("{}/**/*.result.xml", directory.path().display());
+    let loaded = load(&[Source::Glob(pattern)]);
+    assert!(loaded.failures.is_empty());
+    let paths: Vec<&PathBuf> = loaded.results.iter().map(|result| &result.path).collect();
+    assert_eq!(paths, vec![&a, &nested]);
+
+    let loaded = load(&[Source::Glob("[".into())]);
+    assert!(loaded.results.is_empty());
+    assert!(matches!(loaded.failures[0].error, LoadError::Pattern(_)));
+}
+
+#[test]
+fn test_load_with_failures() {
+    let directory = tempfile::tempdir().unwrap();
+    let valid = write(directory.path(), "valid.result.xml", RESULT);
+    let empty = write(directory.path(), "empty.result.xml", NO_RESULT);
+    let broken = write(directory.path(), "broken.result.xml", "<Zusi><result");
+    let missing = directory.path().join("missing.result.xml");
+
+    let loaded = load(&[
+        Source::File(missing.clone()),
+        Source::File(valid.clone()),
+        Source::File(empty.clone()),
+        Source::File(broken.clone()),
+    ]);
+
+    assert_eq!(loaded.results.len(), 1);
+    assert_eq!(loaded.results[0].path, valid);
+
+    assert_eq!(loaded.failures.len(), 3);
+    assert_eq!(loaded.failures[0].path, missing);
+    assert!(matches!(loaded.failures[0].error, LoadError::Io(_)));
+    assert_eq!(loaded.failures[1].path, empty);
+    assert!(matches!(loaded.failures[1].error, LoadError::NoResult));
+    assert_eq!(loaded.failures[2].path, broken);
+    assert!(matches!(loaded.failures[2].error, LoadError::Xml(_)));
+}
+
+#[test]
+fn test_load_keeps_order() {
+    let directory = tempfile::tempdir().unwrap();
+    let paths: Vec<PathBuf> = (0..32)
+        .map(|i| write(directory.path(), &format!("{i:02}.result.xml"), if i % 5 == 0 { NO_RESULT } else { RESULT }))
+        .collect();
+
+    let loaded = load(&[Source::Directory { path: directory.path().to_path_buf(), recursive: false }]);
+
+    let result_paths: Vec<&PathBuf> = loaded.results.iter().map(|result| &result.path).collect();
+    let failure_paths: Vec<&PathBuf> = loaded.failures.iter().map(|failure| &failure.path).collect();
+    assert_eq!(result_paths, paths.iter().enumerate().filter(|(i, _)| i % 5 != 0).map(|(_, path)| path).collect::<Vec<_>>());
+    assert_eq!(failure_paths, paths.iter().step_by(5).collect::<Vec<_>>());
+}
@@ -0,0 +1,52 @@
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+use crate::events::EventKind;
+use crate::result_analyser::ResultAnalyser;
+
+#[cfg(test)]
+mod tests;
+
+/// Key for partitioning runs by their calendar month.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+pub struct YearMonth {
+    pub year: i32,
+    pub month: u8,
+}
+
+/// Key for partitioning runs by their first and last timetable stop.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
+pub struct Route {
+    pub from: String,
+    pub to: String,
+}
+
+/// Partitions runs by the `zugnummer` attribute.
+pub fn train_number<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>) -> String {
+    analyser.result().as_ref().zugnummer.clone()
+}
+
+/// Partitions runs by the month of the `datum` attribute.
+pub fn month<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>) -> YearMonth {
+    let datum = analyser.result().as_ref().datum;
+    YearMonth {
+        year: datum.year(),
+        month: datum.month() as u8,
+    }
+}
+
+/// Partitions runs by the stations of their first and last timetable stop.
+/// Timetable rows without a station are skipped, see [EventKind::is_timetable_stop].
+/// Runs without timetable stops get the key [None].
+pub fn route<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>) -> Option<Route> {
+    let mut stations = analyser.events().filter(|event| event.kind.is_timetable_stop()).filter_map(|event| match event.kind {
+        EventKind::TimetableArrival { station } => Some(station),
+        _ => None,
+    });
+    let from = stations.next()?;
+    let to = stations.last().unwrap_or(from);
+
+    Some(Route {
+        from: from.into(),
+        to: to.into(),
+    })
+}
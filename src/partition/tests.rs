@@ -0,0 +1,83 @@
+use time::macros::datetime;
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::partition::{month, route, train_number, Route, YearMonth};
+use crate::result_analyser::ResultAnalyser;
+use crate::result_analyser_group::ResultAnalyserGroup;
+
+fn result(zugnummer: &str, datum: PrimitiveDateTime, stations: &[&str], fahrt_weg: f32) -> ZusiResult {
+    let mut value = vec![ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_weg(0.)
+        .fahrt_zeit(datum)
+        .build())];
+    value.extend(stations.iter()
+        .map(|station| ResultValue::FahrtEintrag(FahrtEintrag::builder()
+            .fahrt_typ(2)
+            .fahrt_zeit(datum)
+            .fahrt_text(station.to_string())
+            .build())));
+    value.push(ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_weg(fahrt_weg)
+        .fahrt_zeit(datum)
+        .build()));
+
+    ZusiResult::builder()
+        .zugnummer(zugnummer.into())
+        .datum(datum)
+        .value(value)
+        .build()
+}
+
+fn analyser_group() -> ResultAnalyserGroup<ResultAnalyser<ZusiResult>, ZusiResult> {
+    ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result("2083", datetime!(2024-03-23 22:23), &["Kassel Hbf", "Hümme", "Hofgeismar"], 10.)),
+        ResultAnalyser::new(result("2083", datetime!(2024-04-02 8:00), &["Kassel Hbf", "Hofgeismar", ""], 20.)),
+        ResultAnalyser::new(result("24523", datetime!(2024-03-01 7:00), &["Abzw Veddel", "Hannover Hbf"], 30.)),
+        ResultAnalyser::new(result("24523", datetime!(2024-03-02 7:00), &[], 40.)),
+    ]).unwrap()
+}
+
+#[test]
+fn test_partition_by_train_number() {
+    let analyser_group = analyser_group();
+    let partitions = analyser_group.partition_by(train_number);
+
+    assert_eq!(partitions.len(), 2);
+    assert_eq!(partitions["2083"].analysers().len(), 2);
+    assert_eq!(partitions["2083"].total_distance().unwrap(), 30.);
+    assert_eq!(partitions["24523"].average_distance().unwrap(), 35.);
+}
+
+#[test]
+fn test_partition_by_month() {
+    let analyser_group = analyser_group();
+    let partitions = analyser_group.partition_by(month);
+
+    assert_eq!(partitions.keys().collect::<Vec<_>>(), vec![
+        &YearMonth { year: 2024, month: 3 },
+        &YearMonth { year: 2024, month: 4 },
+    ]);
+    assert_eq!(partitions[&YearMonth { year: 2024, month: 3 }].total_distance().unwrap(), 80.);
+}
+
+#[test]
+fn test_partition_by_route() {
+    let analyser_group = analyser_group();
+    let partitions = analyser_group.partition_by(route);
+
+    let kassel_hofgeismar = Some(Route { from: "Kassel Hbf".into(), to: "Hofgeismar".into() });
+    assert_eq!(partitions.len(), 3);
+    assert_eq!(partitions[&kassel_hofgeismar].total_distance().unwrap(), 30.);
+    assert_eq!(partitions[&None].total_distance().unwrap(), 40.);
+}
+
+#[test]
+fn test_partition_by_custom_key() {
+    let analyser_group = analyser_group();
+    let partitions = analyser_group.partition_by(|analyser| analyser.distance().unwrap() > 15.);
+
+    assert_eq!(partitions[&false].analysers().len(), 1);
+    assert_eq!(partitions[&true].analysers().len(), 3);
+}
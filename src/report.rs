@@ -0,0 +1,358 @@
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+use crate::kinematics::{Kinematics, KinematicsOptions};
+use crate::loader::LoadedResult;
+use crate::result_analyser::{AnalyseError, ResultAnalyser};
+use crate::result_analyser_group::ResultAnalyserGroup;
+use crate::segments::Segment;
+use crate::speed_limit::SpeedLimitCompliance;
+use crate::standstill::StandstillSummary;
+use crate::statistics::{Distribution, RunMetric};
+use crate::timetable::Punctuality;
+
+#[cfg(test)]
+mod tests;
+
+/// Provides the file a [ZusiResult] was loaded from, which is added to its [RunReport].
+pub trait SourceFile {
+    fn source_file(&self) -> Option<&Path> {
+        None
+    }
+}
+
+impl SourceFile for ZusiResult {}
+
+impl SourceFile for Segment {}
+
+impl SourceFile for LoadedResult {
+    fn source_file(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+}
+
+impl<T: SourceFile> SourceFile for &T {
+    fn source_file(&self) -> Option<&Path> {
+        (*self).source_file()
+    }
+}
+
+/// Parameters of the metrics in a report which depend on a threshold.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ReportOptions {
+    /// See [speed_limit_compliance](ResultAnalyser::speed_limit_compliance), in m/s.
+    pub speed_limit_tolerance: f32,
+    /// See [punctuality](ResultAnalyser::punctuality).
+    pub punctuality_threshold: Duration,
+    pub kinematics: KinematicsOptions,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            speed_limit_tolerance: 0.,
+            punctuality_threshold: Duration::minutes(5),
+            kinematics: KinematicsOptions::default(),
+        }
+    }
+}
+
+/// All metrics of a single run.
+/// Durations are given in seconds, distances in m, speeds in m/s and energy in kWh.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct RunReport {
+    pub source_file: Option<PathBuf>,
+    pub train_number: String,
+    pub driver_number: String,
+    pub date: PrimitiveDateTime,
+    pub distance: Result<f32, AnalyseError>,
+    pub driving_time: Result<f64, AnalyseError>,
+    pub pure_driving_time: Result<f64, AnalyseError>,
+    pub average_speed: Result<f32, AnalyseError>,
+    pub pure_average_speed: Result<f32, AnalyseError>,
+    pub energy_consumption: f32,
+    pub energy_consumption_per_km: Result<f32, AnalyseError>,
+    pub energy_consumption_per_hour: Result<f32, AnalyseError>,
+    pub standstills: Result<StandstillReport, AnalyseError>,
+    pub speed_limit: Result<SpeedLimitReport, AnalyseError>,
+    pub punctuality: Result<PunctualityReport, AnalyseError>,
+    pub kinematics: Result<KinematicsReport, AnalyseError>,
+    /// See [segments](ResultAnalyser::segments).
+    pub segments: Vec<SegmentReport>,
+}
+
+impl RunReport {
+    pub fn new<R: AsRef<ZusiResult> + SourceFile>(analyser: &ResultAnalyser<R>, options: &ReportOptions) -> RunReport {
+        let result = analyser.result().as_ref();
+        Self {
+            source_file: analyser.result().source_file().map(Path::to_path_buf),
+            train_number: result.zugnummer.clone(),
+            driver_number: result.tf_nummer.clone(),
+            date: result.datum,
+            distance: analyser.distance(),
+            driving_time: analyser.driving_time().map(|duration| duration.as_seconds_f64()),
+            pure_driving_time: analyser.pure_driving_time().map(|duration| duration.as_seconds_f64()),
+            average_speed: analyser.average_speed(),
+            pure_average_speed: analyser.pure_average_speed(),
+            energy_consumption: analyser.energy_consumption(),
+            energy_consumption_per_km: analyser.energy_consumption_per_km(),
+            energy_consumption_per_hour: analyser.energy_consumption_per_hour(),
+            standstills: analyser.standstills()
+                .map(|standstills| StandstillSummary::from_iter(standstills.iter()).into()),
+            speed_limit: analyser.speed_limit_compliance(options.speed_limit_tolerance).map(|compliance| (&compliance).into()),
+            punctuality: analyser.punctuality(options.punctuality_threshold).map(|punctuality| (&punctuality).into()),
+            kinematics: analyser.kinematics(&options.kinematics).map(|kinematics| (&kinematics).into()),
+            segments: analyser.segments().iter().map(SegmentReport::from).collect(),
+        }
+    }
+}
+
+/// Metrics of a segment between two timetable stops, see [Segment].
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SegmentReport {
+    pub from: String,
+    pub to: String,
+    pub scheduled_departure: Option<PrimitiveDateTime>,
+    pub scheduled_arrival: Option<PrimitiveDateTime>,
+    pub scheduled_running_time: Option<f64>,
+    pub distance: Result<f32, AnalyseError>,
+    pub driving_time: Result<f64, AnalyseError>,
+    pub pure_driving_time: Result<f64, AnalyseError>,
+    pub average_speed: Result<f32, AnalyseError>,
+    pub pure_average_speed: Result<f32, AnalyseError>,
+}
+
+impl From<&ResultAnalyser<Segment>> for SegmentReport {
+    fn from(analyser: &ResultAnalyser<Segment>) -> Self {
+        let segment = analyser.result();
+        Self {
+            from: segment.from.clone(),
+            to: segment.to.clone(),
+            scheduled_departure: segment.scheduled_departure,
+            scheduled_arrival: segment.scheduled_arrival,
+            scheduled_running_time: segment.scheduled_running_time().map(|duration| duration.as_seconds_f64()),
+            distance: analyser.distance(),
+            driving_time: analyser.driving_time().map(|duration| duration.as_seconds_f64()),
+            pure_driving_time: analyser.pure_driving_time().map(|duration| duration.as_seconds_f64()),
+            average_speed: analyser.average_speed(),
+            pure_average_speed: analyser.pure_average_speed(),
+        }
+    }
+}
+
+/// All metrics of a group including the reports of its runs.
+/// Units are the same as in [RunReport].
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GroupReport {
+    pub total_distance: Result<f32, AnalyseError>,
+    pub average_distance: Result<f32, AnalyseError>,
+    pub average_speed: Result<f32, AnalyseError>,
+    pub pure_average_speed: Result<f32, AnalyseError>,
+    pub total_driving_time: Result<f64, AnalyseError>,
+    pub total_pure_driving_time: Result<f64, AnalyseError>,
+    pub total_energy_consumption: f32,
+    pub average_energy_consumption_per_km: Result<f32, AnalyseError>,
+    pub standstills: Result<StandstillReport, AnalyseError>,
+    pub punctuality: Result<PunctualityReport, AnalyseError>,
+    pub distributions: DistributionsReport,
+    pub most_energy_efficient: Result<EnergyEfficiencyReport, AnalyseError>,
+    pub least_energy_efficient: Result<EnergyEfficiencyReport, AnalyseError>,
+    pub runs: Vec<RunReport>,
+}
+
+impl GroupReport {
+    pub fn new<A, R>(group: &ResultAnalyserGroup<A, R>, options: &ReportOptions) -> GroupReport
+    where
+        A: AsRef<ResultAnalyser<R>>,
+        R: AsRef<ZusiResult> + SourceFile,
+    {
+        Self {
+            total_distance: group.total_distance(),
+            average_distance: group.average_distance(),
+            average_speed: group.average_speed(),
+            pure_average_speed: group.pure_average_speed(),
+            total_driving_time: group.total_driving_time().map(|duration| duration.as_seconds_f64()),
+            total_pure_driving_time: group.total_pure_driving_time().map(|duration| duration.as_seconds_f64()),
+            total_energy_consumption: group.total_energy_consumption(),
+            average_energy_consumption_per_km: group.average_energy_consumption_per_km(),
+            standstills: group.standstill_summary().map(StandstillReport::from),
+            punctuality: group.punctuality(options.punctuality_threshold).map(|punctuality| (&punctuality).into()),
+            distributions: DistributionsReport {
+                distance: group.distribution(RunMetric::Distance).map(DistributionReport::from),
+                driving_time: group.distribution(RunMetric::DrivingTime).map(DistributionReport::from),
+                pure_driving_time: group.distribution(RunMetric::PureDrivingTime).map(DistributionReport::from),
+                average_speed: group.distribution(RunMetric::AverageSpeed).map(DistributionReport::from),
+                pure_average_speed: group.distribution(RunMetric::PureAverageSpeed).map(DistributionReport::from),
+            },
+            most_energy_efficient: group.most_energy_efficient()
+                .map(|(analyser, consumption)| EnergyEfficiencyReport::new(group, analyser, consumption)),
+            least_energy_efficient: group.least_energy_efficient()
+                .map(|(analyser, consumption)| EnergyEfficiencyReport::new(group, analyser, consumption)),
+            runs: group.analysers().iter()
+                .map(|analyser| RunReport::new(analyser.as_ref(), options))
+                .collect(),
+        }
+    }
+}
+
+/// Distributions of the per-run metrics, see [distribution](ResultAnalyserGroup::distribution).
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DistributionsReport {
+    pub distance: Result<DistributionReport, AnalyseError>,
+    pub driving_time: Result<DistributionReport, AnalyseError>,
+    pub pure_driving_time: Result<DistributionReport, AnalyseError>,
+    pub average_speed: Result<DistributionReport, AnalyseError>,
+    pub pure_average_speed: Result<DistributionReport, AnalyseError>,
+}
+
+/// The runs of the extremes are given as index into [runs](GroupReport::runs).
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DistributionReport {
+    pub min: f32,
+    pub min_run: usize,
+    pub max: f32,
+    pub max_run: usize,
+    pub mean: f32,
+    pub median: f32,
+    pub standard_deviation: f32,
+}
+
+impl From<&Distribution> for DistributionReport {
+    fn from(distribution: &Distribution) -> Self {
+        Self {
+            min: distribution.min().value,
+            min_run: distribution.min().index,
+            max: distribution.max().value,
+            max_run: distribution.max().index,
+            mean: distribution.mean(),
+            median: distribution.median(),
+            standard_deviation: distribution.standard_deviation(),
+        }
+    }
+}
+
+/// The run is given as index into [runs](GroupReport::runs), energy in kWh.
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct EnergyEfficiencyReport {
+    pub run: usize,
+    pub energy_consumption_per_km: f32,
+}
+
+impl EnergyEfficiencyReport {
+    fn new<A, R>(group: &ResultAnalyserGroup<A, R>, analyser: &A, energy_consumption_per_km: f32) -> EnergyEfficiencyReport
+    where
+        A: AsRef<ResultAnalyser<R>>,
+        R: AsRef<ZusiResult>,
+    {
+        Self {
+            // the analyser is borrowed from the group, so it is always found
+            run: group.analysers().iter().position(|other| std::ptr::eq(other, analyser)).unwrap(),
+            energy_consumption_per_km,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct StandstillReport {
+    pub scheduled_stops: usize,
+    pub scheduled_dwell_time: f64,
+    pub unscheduled_stops: usize,
+    pub unscheduled_stop_time: f64,
+}
+
+impl From<StandstillSummary> for StandstillReport {
+    fn from(summary: StandstillSummary) -> Self {
+        Self {
+            scheduled_stops: summary.scheduled_stops,
+            scheduled_dwell_time: summary.scheduled_dwell_time.as_seconds_f64(),
+            unscheduled_stops: summary.unscheduled_stops,
+            unscheduled_stop_time: summary.unscheduled_stop_time.as_seconds_f64(),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SpeedLimitReport {
+    pub time_above_limit: f64,
+    pub distance_above_limit: f32,
+    pub peak_overspeed: f32,
+    pub overspeed_intervals: usize,
+}
+
+impl From<&SpeedLimitCompliance> for SpeedLimitReport {
+    fn from(compliance: &SpeedLimitCompliance) -> Self {
+        Self {
+            time_above_limit: compliance.time_above_limit.as_seconds_f64(),
+            distance_above_limit: compliance.distance_above_limit,
+            peak_overspeed: compliance.peak_overspeed,
+            overspeed_intervals: compliance.intervals.len(),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PunctualityReport {
+    pub stops: usize,
+    pub punctual_stops: usize,
+    pub punctual_share: Option<f32>,
+    pub average_arrival_delay: Option<f64>,
+    pub average_departure_delay: Option<f64>,
+    pub max_delay: Option<f64>,
+}
+
+impl From<&Punctuality> for PunctualityReport {
+    fn from(punctuality: &Punctuality) -> Self {
+        Self {
+            stops: punctuality.stops.len(),
+            punctual_stops: punctuality.punctual_stops(),
+            punctual_share: punctuality.punctual_share(),
+            average_arrival_delay: punctuality.average_arrival_delay().map(|delay| delay.as_seconds_f64()),
+            average_departure_delay: punctuality.average_departure_delay().map(|delay| delay.as_seconds_f64()),
+            max_delay: punctuality.max_delay().map(|delay| delay.as_seconds_f64()),
+        }
+    }
+}
+
+/// Accelerations are given in m/s², jerks in m/s³.
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct KinematicsReport {
+    pub max_acceleration: f32,
+    pub mean_acceleration: f32,
+    pub max_deceleration: f32,
+    pub mean_deceleration: f32,
+    pub time_above_comfort_acceleration: f64,
+    pub time_above_comfort_deceleration: f64,
+    pub braking_events: usize,
+    pub rms_jerk: f32,
+    pub max_jerk: f32,
+}
+
+impl From<&Kinematics> for KinematicsReport {
+    fn from(kinematics: &Kinematics) -> Self {
+        Self {
+            max_acceleration: kinematics.max_acceleration,
+            mean_acceleration: kinematics.mean_acceleration,
+            max_deceleration: kinematics.max_deceleration,
+            mean_deceleration: kinematics.mean_deceleration,
+            time_above_comfort_acceleration: kinematics.time_above_comfort_acceleration.as_seconds_f64(),
+            time_above_comfort_deceleration: kinematics.time_above_comfort_deceleration.as_seconds_f64(),
+            braking_events: kinematics.braking_events.len(),
+            rms_jerk: kinematics.rms_jerk,
+            max_jerk: kinematics.max_jerk,
+        }
+    }
+}
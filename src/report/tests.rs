@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::loader::LoadedResult;
+use crate::report::{EnergyEfficiencyReport, GroupReport, ReportOptions, RunReport, StandstillReport};
+use crate::result_analyser::{AnalyseError, ResultAnalyser};
+use crate::result_analyser_group::ResultAnalyserGroup;
+
+fn result() -> ZusiResult {
+    ZusiResult::builder()
+        .zugnummer("12345".into())
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(3_600_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_speed(20.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01))
+                .build()),
+        ])
+        .build()
+}
+
+#[test]
+fn test_run_report() {
+    let report = RunReport::new(&ResultAnalyser::new(result()), &ReportOptions::default());
+
+    assert_eq!(report.source_file, None);
+    assert_eq!(report.train_number, "12345");
+    assert_eq!(report.date, datetime!(2019-01-01 23:14));
+    assert_eq!(report.distance, Ok(1000.));
+    assert_eq!(report.driving_time, Ok(60.));
+    assert_eq!(report.energy_consumption, 1.);
+    assert_eq!(report.energy_consumption_per_km, Ok(1.));
+    assert_eq!(report.punctuality, Err(AnalyseError::NoTimetableEntries));
+    assert_eq!(report.segments, vec![]);
+    assert_eq!(report.standstills, Ok(StandstillReport {
+        scheduled_stops: 0,
+        scheduled_dwell_time: 0.,
+        unscheduled_stops: 0,
+        unscheduled_stop_time: 0.,
+    }));
+}
+
+#[test]
+fn test_run_report_with_source_file() {
+    let analyser = ResultAnalyser::new(LoadedResult {
+        path: PathBuf::from("data/Ergebnis0.result.xml"),
+        result: result(),
+    });
+
+    let report = RunReport::new(&analyser, &ReportOptions::default());
+    assert_eq!(report.source_file, Some(PathBuf::from("data/Ergebnis0.result.xml")));
+}
+
+#[test]
+fn test_group_report() {
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result()),
+        ResultAnalyser::new(ZusiResult::builder()
+            .datum(datetime!(2019-01-01 23:14))
+            .value(vec![])
+            .build()),
+    ]).unwrap();
+
+    let report = GroupReport::new(&analyser_group, &ReportOptions::default());
+    assert_eq!(report.runs.len(), 2);
+    assert_eq!(report.total_distance, Err(AnalyseError::NoEntries));
+    assert_eq!(report.total_energy_consumption, 1.);
+    assert_eq!(report.runs[0].distance, Ok(1000.));
+    assert_eq!(report.runs[1].distance, Err(AnalyseError::NoEntries));
+    assert_eq!(report.distributions.distance, Err(AnalyseError::NoEntries));
+    assert_eq!(report.most_energy_efficient, Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_group_report_distributions() {
+    let slow = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(7_200_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:02))
+                .build()),
+        ])
+        .build();
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(slow),
+        ResultAnalyser::new(result()),
+    ]).unwrap();
+
+    let report = GroupReport::new(&analyser_group, &ReportOptions::default());
+    let driving_time = report.distributions.driving_time.unwrap();
+    assert_eq!(driving_time.min, 60.);
+    assert_eq!(driving_time.min_run, 1);
+    assert_eq!(driving_time.max, 120.);
+    assert_eq!(driving_time.max_run, 0);
+    assert_eq!(driving_time.median, 90.);
+    assert_eq!(report.distributions.distance.unwrap().standard_deviation, 0.);
+    assert_eq!(report.most_energy_efficient, Ok(EnergyEfficiencyReport {
+        run: 1,
+        energy_consumption_per_km: 1.,
+    }));
+    assert_eq!(report.least_energy_efficient.unwrap().run, 0);
+}
+
+#[test]
+fn test_run_report_segments() {
+    let station = |fahrt_parameter: u32, station: &str, fahrt_weg: f32, minute: u8| ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_typ(2)
+        .fahrt_weg(fahrt_weg)
+        .fahrt_zeit(datetime!(2019-01-01 23:00).replace_minute(minute).unwrap())
+        .fahrt_text(station.into())
+        .fahrt_parameter(fahrt_parameter)
+        .build());
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            station(0, "Hofgeismar", 0., 0),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:01))
+                .build()),
+            station(1, "Hofgeismar", -1., 1),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1500.)
+                .fahrt_speed(20.)
+                .fahrt_zeit(datetime!(2019-01-01 23:03))
+                .build()),
+            station(0, "Hümme", 2000., 4),
+        ])
+        .build();
+
+    let report = RunReport::new(&ResultAnalyser::new(result), &ReportOptions::default());
+    assert_eq!(report.segments.len(), 1);
+    assert_eq!(report.segments[0].from, "Hofgeismar");
+    assert_eq!(report.segments[0].to, "Hümme");
+    assert_eq!(report.segments[0].scheduled_running_time, None);
+    assert_eq!(report.segments[0].distance, Ok(2000.));
+    assert_eq!(report.segments[0].driving_time, Ok(180.));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serialize_run_report() {
+    let report = RunReport::new(&ResultAnalyser::new(result()), &ReportOptions::default());
+    let json = serde_json::to_value(&report).unwrap();
+
+    assert_eq!(json["source_file"], serde_json::Value::Null);
+    assert_eq!(json["train_number"], "12345");
+    assert_eq!(json["distance"]["Ok"], 1000.);
+    assert_eq!(json["driving_time"]["Ok"], 60.);
+    assert_eq!(json["punctuality"]["Err"], "NoTimetableEntries");
+    assert_eq!(json["standstills"]["Ok"]["unscheduled_stops"], 0);
+    assert_eq!(json["segments"], serde_json::json!([]));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serialize_analyse_error() {
+    assert_eq!(serde_json::to_string(&AnalyseError::ZeroDistance).unwrap(), "\"ZeroDistance\"");
+    assert_eq!(serde_json::from_str::<AnalyseError>("\"NoEntries\"").unwrap(), AnalyseError::NoEntries);
+}
@@ -1,119 +1,258 @@
+use std::io::{self, Write};
+use std::sync::OnceLock;
+
 use time::Duration;
 use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
 
+use crate::chart::{speed_chart, ChartOptions};
+use crate::comparison::{compare, Comparison, ComparisonOptions};
+use crate::events::Event;
+use crate::export::{write_csv, CsvOptions};
+use crate::html::{write_run_html, HtmlOptions};
+use crate::kinematics::{kinematics, Kinematics, KinematicsOptions};
+use crate::report::SourceFile;
+use crate::samples::Samples;
+use crate::score::{score, Score, ScoreOptions};
+use crate::segments::{segments, Segment};
+use crate::signals::{signal_log, SignalPassing};
+use crate::speed_limit::{speed_limit_compliance, SpeedLimitCompliance};
+use crate::standstill::{standstills, Standstill};
+use crate::timetable::{punctuality, Punctuality};
+use crate::train_protection::{train_protection, TrainProtection};
+
 #[cfg(test)]
 mod tests;
+mod run_summary;
+
+pub use run_summary::RunSummary;
 
-#[derive(PartialEq, Debug)]
+/// Zusi stores the `verbrauch` attribute in joules.
+const JOULES_PER_KILOWATT_HOUR: f64 = 3_600_000.;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnalyseError {
     NoEntries,
     ZeroDistance,
     ZeroDrivingTime,
+    NoTimetableEntries,
+    InvalidOptions,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Debug)]
 pub struct ResultAnalyser<R> {
     result: R,
-    // TODO: implement cache
+    summary: OnceLock<RunSummary>,
+}
+
+/// Only the results are compared, as the cached summary depends on whether it was already accessed.
+impl<R: PartialEq> PartialEq for ResultAnalyser<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.result == other.result
+    }
 }
 
 impl<R: AsRef<ZusiResult>> ResultAnalyser<R> {
     pub fn new(result: R) -> ResultAnalyser<R> {
         Self {
             result,
+            summary: OnceLock::new(),
         }
     }
 
+    pub fn result(&self) -> &R {
+        &self.result
+    }
+
+    /// Returns the basic metrics of the run.
+    /// They are computed in a single pass on first access and cached afterwards.
+    pub fn summary(&self) -> &RunSummary {
+        self.summary.get_or_init(|| RunSummary::new(self.result.as_ref()))
+    }
+
+    /// Returns a view over all measurement samples of the [ZusiResult].
+    /// All metrics are computed from these samples only, event-only entries are ignored.
+    pub fn samples(&self) -> Samples<'_> {
+        Samples::new(self.result.as_ref())
+    }
+
+    /// Iterates over all events of the [ZusiResult] in their original order.
+    /// Plain measurement samples are skipped, see [Event::from_fahrt_eintrag].
+    pub fn events(&self) -> impl Iterator<Item = Event<'_>> {
+        self.result.as_ref().value.iter().filter_map(|value| {
+            let ResultValue::FahrtEintrag(fahrt_eintrag) = value;
+            Event::from_fahrt_eintrag(fahrt_eintrag)
+        })
+    }
+
     /// Computes the distance for the whole route by using the `fahrt_weg` attribute.
     ///
-    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
     pub fn distance(&self) -> Result<f32, AnalyseError> {
-        let result = self.result.as_ref();
-        if result.value.len() > 0 {
-            let ResultValue::FahrtEintrag(first) = result.value.first().unwrap();
-            let ResultValue::FahrtEintrag(last) = result.value.last().unwrap();
-            Ok(last.fahrt_weg - first.fahrt_weg)
-        } else {
-            Err(AnalyseError::NoEntries)
-        }
+        self.summary().distance()
     }
 
     /// Computes the average speed including idle times by using the overall driving time and distance.
     ///
     /// Throws [AnalyseError::ZeroDrivingTime] if the computed driving time is zero.
     pub fn average_speed(&self) -> Result<f32, AnalyseError> {
-        let distance = self.distance()?;
-        let driving_time = self.driving_time()?.as_seconds_f32();
-        if driving_time == 0.0 {
-            Err(AnalyseError::ZeroDrivingTime)
-        } else {
-            Ok(distance / driving_time)
-        }
+        self.summary().average_speed()
     }
 
     /// Computes the average speed excluding idle times.
-    /// For each two [FahrtEintrag](ResultValue::FahrtEintrag) entries, the average speed between is computed.
+    /// For each two consecutive measurement samples, the average speed between is computed.
     /// All these local average speeds will be averaged together weighted by their individual local distance.
     ///
-    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
     pub fn pure_average_speed(&self) -> Result<f32, AnalyseError> {
-        let result = self.result.as_ref();
-        if self.distance()? == 0. {
-            Err(AnalyseError::ZeroDistance)
-        } else if result.value.len() > 1 {
-            let mut weighted_speed_sum = 0.;
-            for i in 0..result.value.len() - 1 {
-                let ResultValue::FahrtEintrag(current) = result.value.get(i).unwrap();
-                let ResultValue::FahrtEintrag(next) = result.value.get(i + 1).unwrap();
-                let local_average_speed = (current.fahrt_speed + next.fahrt_speed) / 2.;
-                let local_distance = next.fahrt_weg - current.fahrt_weg;
-                weighted_speed_sum += local_distance * local_average_speed;
-            }
-            Ok(weighted_speed_sum / self.distance()?)
-        } else {
-            Err(AnalyseError::NoEntries)
-        }
+        self.summary().pure_average_speed()
     }
 
     /// Computes the whole driving time including idle times by using the `fahrt_zeit` attribute.
     ///
-    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
     pub fn driving_time(&self) -> Result<Duration, AnalyseError> {
-        let result = self.result.as_ref();
-        if result.value.len() > 0 {
-            let ResultValue::FahrtEintrag(first) = result.value.first().unwrap();
-            let ResultValue::FahrtEintrag(last) = result.value.last().unwrap();
-            Ok(last.fahrt_zeit - first.fahrt_zeit)
-        } else {
-            Err(AnalyseError::NoEntries)
-        }
+        self.summary().driving_time()
     }
 
     /// Computes the whole driving time excluding idle times by omitting all periods with zero driving speed.
     ///
-    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any [FahrtEintrag](ResultValue::FahrtEintrag) entries.
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
     pub fn pure_driving_time(&self) -> Result<Duration, AnalyseError> {
-        let result = self.result.as_ref();
-        if result.value.len() > 1 {
-            let mut driving_time = Duration::seconds(0);
-            for i in 0..result.value.len() - 1 {
-                let ResultValue::FahrtEintrag(current) = result.value.get(i).unwrap();
-                let ResultValue::FahrtEintrag(next) = result.value.get(i + 1).unwrap();
-                if current.fahrt_speed > 0. || next.fahrt_speed > 0. {
-                    driving_time += next.fahrt_zeit - current.fahrt_zeit;
-                }
-            }
-            Ok(driving_time)
-        } else if result.value.len() > 0 {
-            Ok(Duration::seconds(0))
+        self.summary().pure_driving_time()
+    }
+
+    /// Returns the energy consumption of the whole route in kWh by using the `verbrauch` attribute.
+    pub fn energy_consumption(&self) -> f32 {
+        (self.result.as_ref().verbrauch / JOULES_PER_KILOWATT_HOUR) as f32
+    }
+
+    /// Computes the energy consumption per km in kWh/km by using the [distance](ResultAnalyser::distance).
+    ///
+    /// Throws [AnalyseError::ZeroDistance] if the computed distance is zero.
+    pub fn energy_consumption_per_km(&self) -> Result<f32, AnalyseError> {
+        let distance = self.distance()?;
+        if distance == 0. {
+            Err(AnalyseError::ZeroDistance)
+        } else {
+            Ok(self.energy_consumption() / (distance / 1000.))
+        }
+    }
+
+    /// Computes the energy consumption per hour in kWh/h by using the [pure driving time](ResultAnalyser::pure_driving_time).
+    ///
+    /// Throws [AnalyseError::ZeroDrivingTime] if the computed pure driving time is zero.
+    pub fn energy_consumption_per_hour(&self) -> Result<f32, AnalyseError> {
+        let pure_driving_time = self.pure_driving_time()?.as_seconds_f32();
+        if pure_driving_time == 0. {
+            Err(AnalyseError::ZeroDrivingTime)
         } else {
-            Err(AnalyseError::NoEntries)
+            Ok(self.energy_consumption() / (pure_driving_time / 3600.))
         }
     }
+
+    /// Splits the run into segments between consecutive timetable stops.
+    /// Each segment offers the same metrics as the whole run and can be used as input for a [ResultAnalyserGroup](crate::result_analyser_group::ResultAnalyserGroup).
+    /// For more details see [crate::segments::segments].
+    pub fn segments(&self) -> Vec<ResultAnalyser<Segment>> {
+        segments(self.result.as_ref())
+    }
+
+    /// Derives acceleration, braking events and jerk from the speed of consecutive measurement samples.
+    /// For more details see [crate::kinematics::kinematics].
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
+    pub fn kinematics(&self, options: &KinematicsOptions) -> Result<Kinematics, AnalyseError> {
+        kinematics(&self.samples(), options)
+    }
+
+    /// Compares the speed against the lowest active speed limit of track, signals and train protection.
+    /// The speed may exceed the limit by `tolerance` (m/s) without counting as overspeed.
+    /// For more details see [crate::speed_limit::speed_limit_compliance].
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
+    pub fn speed_limit_compliance(&self, tolerance: f32) -> Result<SpeedLimitCompliance, AnalyseError> {
+        speed_limit_compliance(&self.samples(), tolerance)
+    }
+
+    /// Compares the actual arrival and departure times at all timetable stops with the scheduled ones.
+    /// A stop counts as punctual if its delay does not exceed `threshold`.
+    /// For more details see [crate::timetable::punctuality].
+    ///
+    /// Throws [AnalyseError::NoTimetableEntries] if the [ZusiResult] does not contain any timetable stops.
+    pub fn punctuality(&self, threshold: Duration) -> Result<Punctuality, AnalyseError> {
+        punctuality(self.events(), threshold)
+    }
+
+    /// Grades the run by punctuality, speed limit compliance, smoothness, unscheduled stops and energy consumption.
+    /// For more details see [crate::score::score].
+    ///
+    /// Throws [AnalyseError::InvalidOptions] if the energy target is not positive, the penalty or a weight is negative or all weights are zero,
+    /// [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples,
+    /// [AnalyseError::ZeroDrivingTime] if the train did not move
+    /// and [AnalyseError::ZeroDistance] if an energy target is set but the distance is zero.
+    pub fn score(&self, options: &ScoreOptions) -> Result<Score, AnalyseError> {
+        score(self, options)
+    }
+
+    /// Compares another run of the same route with this run as reference.
+    /// For more details see [crate::comparison::compare].
+    ///
+    /// Throws [AnalyseError::InvalidOptions] if a length in the options is not positive,
+    /// [AnalyseError::NoEntries] if a [ZusiResult] does not contain any measurement samples
+    /// and [AnalyseError::ZeroDistance] if the runs do not share any part of the route.
+    pub fn compare<C: AsRef<ZusiResult>>(&self, compared: &ResultAnalyser<C>, options: &ComparisonOptions) -> Result<Comparison, AnalyseError> {
+        compare(self, compared, options)
+    }
+
+    /// Counts the influences, acknowledgements, restrictive supervisions and forced brakes of the train protection system.
+    /// For more details see [crate::train_protection::train_protection].
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
+    pub fn train_protection(&self) -> Result<TrainProtection, AnalyseError> {
+        train_protection(self.result.as_ref())
+    }
+
+    /// Lists all passed signals with their speed and the signal limit in force before them.
+    /// For more details see [crate::signals::signal_log].
+    pub fn signal_log(&self, tolerance: f32) -> Vec<SignalPassing> {
+        signal_log(self.result.as_ref(), tolerance)
+    }
+
+    /// Writes the measurement samples and optionally the events as CSV.
+    /// For more details see [crate::export::write_csv].
+    pub fn write_csv<W: Write>(&self, options: &CsvOptions, out: W) -> io::Result<()> {
+        write_csv(self.result.as_ref(), options, out)
+    }
+
+    /// Writes a self-contained HTML page with all metrics, stops, overspeed periods and the speed chart.
+    /// For more details see [crate::html::write_run_html].
+    pub fn write_html<W: Write>(&self, options: &HtmlOptions, out: W) -> io::Result<()>
+    where
+        R: SourceFile,
+    {
+        write_run_html(self, options, out)
+    }
+
+    /// Renders the speed over the distance together with the speed limits and stations as a standalone SVG image.
+    /// For more details see [crate::chart::overlay_chart].
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
+    pub fn speed_chart(&self, options: &ChartOptions) -> Result<String, AnalyseError> {
+        speed_chart(self.result.as_ref(), options)
+    }
+
+    /// Detects all periods in which the train did not move and classifies them as timetable or unscheduled stops.
+    /// For more details see [crate::standstill::standstills].
+    ///
+    /// Throws [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples.
+    pub fn standstills(&self) -> Result<Vec<Standstill>, AnalyseError> {
+        standstills(&self.samples(), self.events())
+    }
 }
 
 impl<R: AsRef<ZusiResult>> AsRef<ResultAnalyser<R>> for ResultAnalyser<R> {
     fn as_ref(&self) -> &ResultAnalyser<R> {
-        &self
+        self
     }
-}
\ No newline at end of file
+}
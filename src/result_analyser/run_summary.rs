@@ -0,0 +1,109 @@
+use time::Duration;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+
+use crate::result_analyser::AnalyseError;
+use crate::samples::is_measurement;
+
+/// All basic metrics of a single run, computed in one pass over its entries.
+/// Event-only entries are ignored.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct RunSummary {
+    samples: usize,
+    distance: f32,
+    weighted_speed_sum: f32,
+    driving_time: Duration,
+    pure_driving_time: Duration,
+}
+
+impl RunSummary {
+    pub fn new(result: &ZusiResult) -> RunSummary {
+        let mut summary = Self {
+            samples: 0,
+            distance: 0.,
+            weighted_speed_sum: 0.,
+            driving_time: Duration::seconds(0),
+            pure_driving_time: Duration::seconds(0),
+        };
+
+        let mut first = None;
+        let mut previous = None;
+
+        for value in result.value.iter() {
+            let ResultValue::FahrtEintrag(current) = value;
+            if !is_measurement(current) {
+                continue;
+            }
+
+            let first = *first.get_or_insert(current);
+            if let Some(previous) = previous.replace(current) {
+                let local_average_speed = (previous.fahrt_speed + current.fahrt_speed) / 2.;
+                let local_distance = current.fahrt_weg - previous.fahrt_weg;
+                summary.weighted_speed_sum += local_distance * local_average_speed;
+                if previous.fahrt_speed > 0. || current.fahrt_speed > 0. {
+                    summary.pure_driving_time += current.fahrt_zeit - previous.fahrt_zeit;
+                }
+            }
+
+            summary.samples += 1;
+            summary.distance = current.fahrt_weg - first.fahrt_weg;
+            summary.driving_time = current.fahrt_zeit - first.fahrt_zeit;
+        }
+
+        summary
+    }
+
+    /// Number of measurement samples.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// See [distance](crate::result_analyser::ResultAnalyser::distance).
+    pub fn distance(&self) -> Result<f32, AnalyseError> {
+        if self.samples > 0 {
+            Ok(self.distance)
+        } else {
+            Err(AnalyseError::NoEntries)
+        }
+    }
+
+    /// See [average_speed](crate::result_analyser::ResultAnalyser::average_speed).
+    pub fn average_speed(&self) -> Result<f32, AnalyseError> {
+        let distance = self.distance()?;
+        let driving_time = self.driving_time()?.as_seconds_f32();
+        if driving_time == 0.0 {
+            Err(AnalyseError::ZeroDrivingTime)
+        } else {
+            Ok(distance / driving_time)
+        }
+    }
+
+    /// See [pure_average_speed](crate::result_analyser::ResultAnalyser::pure_average_speed).
+    pub fn pure_average_speed(&self) -> Result<f32, AnalyseError> {
+        let distance = self.distance()?;
+        if distance == 0. {
+            Err(AnalyseError::ZeroDistance)
+        } else if self.samples > 1 {
+            Ok(self.weighted_speed_sum / distance)
+        } else {
+            Err(AnalyseError::NoEntries)
+        }
+    }
+
+    /// See [driving_time](crate::result_analyser::ResultAnalyser::driving_time).
+    pub fn driving_time(&self) -> Result<Duration, AnalyseError> {
+        if self.samples > 0 {
+            Ok(self.driving_time)
+        } else {
+            Err(AnalyseError::NoEntries)
+        }
+    }
+
+    /// See [pure_driving_time](crate::result_analyser::ResultAnalyser::pure_driving_time).
+    pub fn pure_driving_time(&self) -> Result<Duration, AnalyseError> {
+        if self.samples > 0 {
+            Ok(self.pure_driving_time)
+        } else {
+            Err(AnalyseError::NoEntries)
+        }
+    }
+}
@@ -35,6 +35,24 @@ fn test_distance_2() {
     assert_eq!(analyser.distance().unwrap(), 20.1);
 }
 
+#[test]
+fn test_equality_ignores_cache() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2.33)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(&result);
+    let other = ResultAnalyser::new(&result);
+    analyser.distance().unwrap();
+    assert_eq!(analyser, other);
+}
+
 #[test]
 fn test_distance_0() {
     let result = ZusiResult::builder()
@@ -286,4 +304,155 @@ fn test_pure_driving_time_0() {
 
     let analyser = ResultAnalyser::new(result);
     assert_eq!(analyser.pure_driving_time(), Err(AnalyseError::NoEntries));
-}
\ No newline at end of file
+}
+#[test]
+fn test_trailing_event_entries() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(8)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18:30))
+                .fahrt_speed(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(15.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(30.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .fahrt_speed(-1.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.distance().unwrap(), 10.);
+    assert_eq!(analyser.driving_time().unwrap(), Duration::minutes(1));
+    assert_eq!(analyser.pure_average_speed().unwrap(), 20.);
+    assert_eq!(analyser.pure_driving_time().unwrap(), Duration::minutes(1));
+}
+
+#[test]
+fn test_only_event_entries() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(8)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(4)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(-1.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.distance(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser.average_speed(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser.pure_average_speed(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser.driving_time(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser.pure_driving_time(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_energy_consumption() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(72_000_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:30))
+                .fahrt_speed(0.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:50))
+                .fahrt_speed(0.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.energy_consumption(), 20.);
+    assert_eq!(analyser.energy_consumption_per_km().unwrap(), 5.);
+    assert_eq!(analyser.energy_consumption_per_hour().unwrap(), 40.);
+}
+
+#[test]
+fn test_energy_consumption_zero() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(72_000_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.energy_consumption_per_km(), Err(AnalyseError::ZeroDistance));
+    assert_eq!(analyser.energy_consumption_per_hour(), Err(AnalyseError::ZeroDrivingTime));
+}
+
+#[test]
+fn test_summary() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(5.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed(10.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(8)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(15.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .fahrt_speed(30.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(35.)
+                .fahrt_zeit(datetime!(2019-01-01 23:22))
+                .fahrt_speed(100.)
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    let summary = analyser.summary();
+    assert_eq!(summary.samples(), 3);
+    assert_eq!(summary.distance().unwrap(), 30.);
+    assert_eq!(summary.pure_average_speed().unwrap(), 50.);
+    assert_eq!(summary.driving_time().unwrap(), Duration::minutes(4));
+    assert_eq!(summary.pure_driving_time().unwrap(), Duration::minutes(4));
+    assert!(std::ptr::eq(summary, analyser.summary()));
+}
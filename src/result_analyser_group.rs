@@ -1,9 +1,20 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
 use std::marker::PhantomData;
 use time::Duration;
 use zusi_xml_lib::xml::zusi::result::ZusiResult;
 
-use crate::result_analyser::{AnalyseError, ResultAnalyser};
-use crate::result_analyser_group::analyser_group_cache::AnalyserGroupCache;
+use crate::chart::{overlay_chart, ChartOptions};
+use crate::html::{write_group_html, HtmlOptions};
+use crate::report::SourceFile;
+use crate::result_analyser::{AnalyseError, ResultAnalyser, RunSummary};
+use crate::result_analyser_group::analyser_group_cache::{AnalyserGroupCache, Contribution};
+use crate::score::{Score, ScoreOptions};
+use crate::signals::{signal_statistics, SignalStatistics};
+use crate::standstill::{Standstill, StandstillSummary};
+use crate::statistics::{Distribution, RunMetric};
+use crate::timetable::Punctuality;
+use crate::train_protection::{TrainProtection, TrainProtectionSummary};
 
 #[cfg(test)]
 mod tests;
@@ -14,16 +25,23 @@ pub enum CreateAnalyserGroupError {
     NoAnalysers,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Debug)]
 pub struct ResultAnalyserGroup<A, R> {
     analysers: Vec<A>,
     cache: AnalyserGroupCache,
-    _phantom: PhantomData<R>,
+    _phantom: PhantomData<fn() -> R>,
+}
+
+/// Only the analysers are compared, as the cache depends on which values were already accessed.
+impl<A: PartialEq, R> PartialEq for ResultAnalyserGroup<A, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.analysers == other.analysers
+    }
 }
 
 impl<A: AsRef<ResultAnalyser<R>>, R: AsRef<ZusiResult>> ResultAnalyserGroup<A, R> {
     pub fn new(analysers: Vec<A>) -> Result<ResultAnalyserGroup<A, R>, CreateAnalyserGroupError> {
-        if analysers.len() == 0 {
+        if analysers.is_empty() {
             Err(CreateAnalyserGroupError::NoAnalysers)
         } else {
             Ok(Self {
@@ -34,117 +52,450 @@ impl<A: AsRef<ResultAnalyser<R>>, R: AsRef<ZusiResult>> ResultAnalyserGroup<A, R
         }
     }
 
-    /// Computes the sum of the distance values for all routes.
-    /// For more details see [distance](ResultAnalyser::distance).
+    pub fn analysers(&self) -> &[A] {
+        &self.analysers
+    }
+
+    /// Appends an analyser to the group.
+    /// Cached totals like [total_distance](ResultAnalyserGroup::total_distance) are updated without a full recomputation,
+    /// all other cached values are invalidated.
+    pub fn push(&mut self, analyser: A) {
+        self.cache.add(Self::contribution(&analyser));
+        self.analysers.push(analyser);
+    }
+
+    /// Appends all analysers of the iterator to the group.
+    /// For more details see [push](ResultAnalyserGroup::push).
+    pub fn extend<I: IntoIterator<Item = A>>(&mut self, analysers: I) {
+        for analyser in analysers {
+            self.push(analyser);
+        }
+    }
+
+    /// Removes and returns the analyser at `index`.
+    /// Cached totals are updated without a full recomputation, all other cached values are invalidated.
     ///
-    /// Errors will be propagated.
-    pub fn total_distance(&mut self) -> Result<f32, AnalyseError> {
-        if let Some(value) = &self.cache.total_distance {
-            return Ok(*value);
+    /// Throws [CreateAnalyserGroupError::NoAnalysers] if the group would become empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Result<A, CreateAnalyserGroupError> {
+        assert!(index < self.analysers.len(), "index {index} out of bounds");
+        if self.analysers.len() == 1 {
+            return Err(CreateAnalyserGroupError::NoAnalysers);
         }
 
-        let mut total_distance = 0.;
+        let analyser = self.analysers.remove(index);
+        self.cache.subtract(Self::contribution(&analyser));
+        Ok(analyser)
+    }
+
+    /// Keeps only the analysers matching the predicate.
+    /// For more details see [remove](ResultAnalyserGroup::remove).
+    ///
+    /// Throws [CreateAnalyserGroupError::NoAnalysers] if no analyser matches, the group stays unchanged in this case.
+    pub fn retain<F: Fn(&ResultAnalyser<R>) -> bool>(&mut self, predicate: F) -> Result<(), CreateAnalyserGroupError> {
+        let keep: Vec<bool> = self.analysers.iter().map(|analyser| predicate(analyser.as_ref())).collect();
+        if !keep.contains(&true) {
+            return Err(CreateAnalyserGroupError::NoAnalysers);
+        }
+
+        let analysers = std::mem::take(&mut self.analysers);
+        for (analyser, keep) in analysers.into_iter().zip(keep) {
+            if keep {
+                self.analysers.push(analyser);
+            } else {
+                self.cache.subtract(Self::contribution(&analyser));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies `f` to all analysers and keeps their order.
+    /// With the `parallel` feature [par_map](Self::par_map) does the same in parallel.
+    fn map_analysers<'a, T, F: Fn(&'a ResultAnalyser<R>) -> T>(&'a self, f: F) -> Vec<T> {
+        self.analysers.iter().map(|analyser| f(analyser.as_ref())).collect()
+    }
+
+    /// Returns the cached [RunSummary] of all analysers, computing missing ones first.
+    fn summaries(&self) -> Vec<&RunSummary> {
+        self.map_analysers(|analyser| analyser.summary())
+    }
+
+    fn contribution(analyser: &A) -> Contribution {
+        let analyser = analyser.as_ref();
+        Contribution::new(analyser.summary(), analyser.energy_consumption())
+    }
+
+    /// Creates a sub-group of all analysers matching the predicate.
+    /// Built-in predicates can be found in [crate::filters], e.g. [min_distance](crate::filters::min_distance).
+    /// The sub-group borrows its analysers and has its own cache.
+    ///
+    /// Throws [CreateAnalyserGroupError::NoAnalysers] if no analyser matches.
+    pub fn filter<F: Fn(&ResultAnalyser<R>) -> bool>(&self, predicate: F) -> Result<ResultAnalyserGroup<&A, R>, CreateAnalyserGroupError> {
+        ResultAnalyserGroup::new(
+            self.analysers.iter().filter(|analyser| predicate(analyser.as_ref())).collect()
+        )
+    }
+
+    /// Creates a sub-group of all analysers whose [ZusiResult] matches the predicate.
+    /// For more details see [filter](ResultAnalyserGroup::filter).
+    ///
+    /// Throws [CreateAnalyserGroupError::NoAnalysers] if no analyser matches.
+    pub fn filter_results<F: Fn(&ZusiResult) -> bool>(&self, predicate: F) -> Result<ResultAnalyserGroup<&A, R>, CreateAnalyserGroupError> {
+        self.filter(|analyser| predicate(analyser.result().as_ref()))
+    }
+
+    /// Splits the group into sub-groups of all analysers with the same key.
+    /// Built-in keys can be found in [crate::partition], e.g. [train_number](crate::partition::train_number).
+    /// Each sub-group borrows its analysers and has its own cache.
+    pub fn partition_by<K: Ord, F: Fn(&ResultAnalyser<R>) -> K>(&self, key: F) -> BTreeMap<K, ResultAnalyserGroup<&A, R>> {
+        let mut partitions: BTreeMap<K, Vec<&A>> = BTreeMap::new();
 
         for analyser in self.analysers.iter() {
-            total_distance += analyser.as_ref().distance()?;
+            partitions.entry(key(analyser.as_ref())).or_default().push(analyser);
         }
 
-        self.cache.total_distance = Some(total_distance);
-        Ok(total_distance)
+        partitions.into_iter()
+            // every partition contains at least one analyser
+            .map(|(key, analysers)| (key, ResultAnalyserGroup::new(analysers).unwrap()))
+            .collect()
     }
 
-    /// Computes the average distance per route.
+    /// Computes the sum of the distance values for all routes.
+    /// For more details see [distance](ResultAnalyser::distance).
     ///
     /// Errors will be propagated.
-    pub fn average_distance(&mut self) -> Result<f32, AnalyseError> {
-        if let Some(value) = &self.cache.average_distance {
-            return Ok(*value);
-        }
+    pub fn total_distance(&self) -> Result<f32, AnalyseError> {
+        *self.cache.total_distance.get_or_init(|| {
+            let mut total_distance = 0.;
 
-        // analysers.len() can't be zero due to a check on creation.
-        let average_distance = self.total_distance()? / self.analysers.len() as f32;
+            for summary in self.summaries() {
+                total_distance += summary.distance()?;
+            }
 
-        self.cache.average_distance = Some(average_distance);
-        Ok(average_distance)
+            Ok(total_distance)
+        })
+    }
+
+    /// Computes the average distance per route.
+    ///
+    /// Errors will be propagated.
+    pub fn average_distance(&self) -> Result<f32, AnalyseError> {
+        *self.cache.average_distance.get_or_init(|| {
+            // analysers.len() can't be zero due to a check on creation.
+            let average_distance = self.total_distance()? / self.analysers.len() as f32;
+
+            Ok(average_distance)
+        })
     }
 
     /// Computes the average speed for all routes including idle times.
     /// For more details see [distance](ResultAnalyser::average_speed).
     ///
     /// Errors will be propagated.
-    pub fn average_speed(&mut self) -> Result<f32, AnalyseError> {
-        if let Some(value) = &self.cache.average_speed {
-            return Ok(*value);
-        }
+    pub fn average_speed(&self) -> Result<f32, AnalyseError> {
+        *self.cache.average_speed.get_or_init(|| {
+            let mut weighted_speed_sum = 0.;
+            for summary in self.summaries() {
+                weighted_speed_sum += summary.distance()? * summary.average_speed()?;
+            }
 
-        let mut weighted_speed_sum = 0.;
-        for analyser in self.analysers.iter() {
-            weighted_speed_sum += analyser.as_ref().distance()? * analyser.as_ref().average_speed()?;
-        }
+            let average_speed = weighted_speed_sum / self.total_distance()?;
 
-        let average_speed = weighted_speed_sum / self.total_distance()?;
-
-        self.cache.average_speed = Some(average_speed);
-        Ok(average_speed)
+            Ok(average_speed)
+        })
     }
 
     /// Computes the average speed for all routes excluding idle times.
     /// For more details see [distance](ResultAnalyser::pure_average_speed).
     ///
     /// Errors will be propagated.
-    pub fn pure_average_speed(&mut self) -> Result<f32, AnalyseError> {
-        if let Some(value) = &self.cache.pure_average_speed {
-            return Ok(*value);
-        }
+    pub fn pure_average_speed(&self) -> Result<f32, AnalyseError> {
+        *self.cache.pure_average_speed.get_or_init(|| {
+            let mut weighted_speed_sum = 0.;
+            for summary in self.summaries() {
+                weighted_speed_sum += summary.distance()? * summary.pure_average_speed()?;
+            }
 
-        let mut weighted_speed_sum = 0.;
-        for analyser in self.analysers.iter() {
-            weighted_speed_sum += analyser.as_ref().distance()? * analyser.as_ref().pure_average_speed()?;
-        }
+            let pure_average_speed = weighted_speed_sum / self.total_distance()?;
 
-        let pure_average_speed = weighted_speed_sum / self.total_distance()?;
-
-        self.cache.pure_average_speed = Some(pure_average_speed);
-        Ok(pure_average_speed)
+            Ok(pure_average_speed)
+        })
     }
 
     /// Computes the sum of the driving times including idle times for all routes.
     /// For more details see [distance](ResultAnalyser::driving_time).
     ///
     /// Errors will be propagated.
-    pub fn total_driving_time(&mut self) -> Result<Duration, AnalyseError> {
-        if let Some(value) = &self.cache.total_driving_time {
-            return Ok(*value);
-        }
-
-        let mut total_driving_time = Duration::seconds(0);
+    pub fn total_driving_time(&self) -> Result<Duration, AnalyseError> {
+        *self.cache.total_driving_time.get_or_init(|| {
+            let mut total_driving_time = Duration::seconds(0);
 
-        for analyser in self.analysers.iter() {
-            total_driving_time += analyser.as_ref().driving_time()?;
-        }
+            for summary in self.summaries() {
+                total_driving_time += summary.driving_time()?;
+            }
 
-        self.cache.total_driving_time = Some(total_driving_time);
-        Ok(total_driving_time)
+            Ok(total_driving_time)
+        })
     }
 
     /// Computes the sum of the driving times excluding idle times for all routes.
     /// For more details see [distance](ResultAnalyser::pure_driving_time).
     ///
     /// Errors will be propagated.
-    pub fn total_pure_driving_time(&mut self) -> Result<Duration, AnalyseError> {
-        if let Some(value) = &self.cache.total_pure_driving_time {
-            return Ok(*value);
-        }
+    pub fn total_pure_driving_time(&self) -> Result<Duration, AnalyseError> {
+        *self.cache.total_pure_driving_time.get_or_init(|| {
+            let mut total_pure_driving_time = Duration::seconds(0);
 
-        let mut total_pure_driving_time = Duration::seconds(0);
+            for summary in self.summaries() {
+                total_pure_driving_time += summary.pure_driving_time()?;
+            }
 
-        for analyser in self.analysers.iter() {
-            total_pure_driving_time += analyser.as_ref().pure_driving_time()?;
+            Ok(total_pure_driving_time)
+        })
+    }
+
+    /// Computes min, max, median, percentiles and standard deviation of a metric across all routes.
+    /// The [index](crate::statistics::Extreme::index) of an extreme refers to [analysers](ResultAnalyserGroup::analysers).
+    ///
+    /// Errors will be propagated.
+    pub fn distribution(&self, metric: RunMetric) -> Result<&Distribution, AnalyseError> {
+        let position = RunMetric::ALL.iter().position(|m| *m == metric).unwrap();
+        self.cache.distributions[position]
+            .get_or_init(|| {
+                let values = self.summaries().into_iter()
+                    .map(|summary| metric.value(summary))
+                    .collect::<Result<Vec<f32>, AnalyseError>>()?;
+
+                // analysers.len() can't be zero due to a check on creation.
+                Ok(Distribution::new(values).unwrap())
+            })
+            .as_ref()
+            .map_err(|error| *error)
+    }
+
+    /// Computes the sum of the energy consumption in kWh for all routes.
+    /// For more details see [energy_consumption](ResultAnalyser::energy_consumption).
+    pub fn total_energy_consumption(&self) -> f32 {
+        *self.cache.total_energy_consumption.get_or_init(|| {
+            self.analysers.iter()
+                .map(|analyser| analyser.as_ref().energy_consumption())
+                .sum()
+        })
+    }
+
+    /// Computes the energy consumption per km in kWh/km for all routes weighted by their distance.
+    /// For more details see [energy_consumption_per_km](ResultAnalyser::energy_consumption_per_km).
+    ///
+    /// Throws [AnalyseError::ZeroDistance] if the total distance is zero, other errors will be propagated.
+    pub fn average_energy_consumption_per_km(&self) -> Result<f32, AnalyseError> {
+        *self.cache.average_energy_consumption_per_km.get_or_init(|| {
+            let total_distance = self.total_distance()?;
+            if total_distance == 0. {
+                return Err(AnalyseError::ZeroDistance);
+            }
+            let average_energy_consumption_per_km = self.total_energy_consumption() / (total_distance / 1000.);
+
+            Ok(average_energy_consumption_per_km)
+        })
+    }
+
+    /// Finds the route with the lowest energy consumption per km.
+    /// For more details see [energy_consumption_per_km](ResultAnalyser::energy_consumption_per_km).
+    ///
+    /// Errors will be propagated.
+    pub fn most_energy_efficient(&self) -> Result<(&A, f32), AnalyseError> {
+        self.energy_consumptions_per_km()?
+            .into_iter()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .ok_or(AnalyseError::NoEntries)
+    }
+
+    /// Finds the route with the highest energy consumption per km.
+    /// For more details see [energy_consumption_per_km](ResultAnalyser::energy_consumption_per_km).
+    ///
+    /// Errors will be propagated.
+    pub fn least_energy_efficient(&self) -> Result<(&A, f32), AnalyseError> {
+        self.energy_consumptions_per_km()?
+            .into_iter()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .ok_or(AnalyseError::NoEntries)
+    }
+
+    fn energy_consumptions_per_km(&self) -> Result<Vec<(&A, f32)>, AnalyseError> {
+        self.analysers.iter()
+            .map(|analyser| Ok((analyser, analyser.as_ref().energy_consumption_per_km()?)))
+            .collect()
+    }
+
+    /// Scores all routes and sorts them from the highest to the lowest total score.
+    /// Routes with the same score keep their order.
+    /// For more details see [score](ResultAnalyser::score).
+    ///
+    /// Errors will be propagated.
+    pub fn rank_by_score(&self, options: &ScoreOptions) -> Result<Vec<(&A, Score)>, AnalyseError> {
+        rank(&self.analysers, self.map_analysers(|analyser| analyser.score(options)))
+    }
+
+    /// Computes the totals of scheduled dwell time and unscheduled stop time for all routes.
+    /// For more details see [standstills](ResultAnalyser::standstills).
+    ///
+    /// Errors will be propagated.
+    pub fn standstill_summary(&self) -> Result<StandstillSummary, AnalyseError> {
+        *self.cache.standstill_summary.get_or_init(|| sum_standstills(self.map_analysers(|analyser| analyser.standstills())))
+    }
+
+    /// Computes the totals of train protection influences, acknowledgements, restrictive supervisions and forced brakes for all routes.
+    /// For more details see [train_protection](ResultAnalyser::train_protection).
+    ///
+    /// Errors will be propagated.
+    pub fn train_protection_summary(&self) -> Result<TrainProtectionSummary, AnalyseError> {
+        *self.cache.train_protection_summary.get_or_init(|| sum_train_protection(self.map_analysers(|analyser| analyser.train_protection())))
+    }
+
+    /// Computes the passing speeds of every signal over all routes, keyed by the name of the signal.
+    /// Signals which are approached too fast stand out by their maximum speed or share above the limit.
+    /// For more details see [signal_log](ResultAnalyser::signal_log).
+    pub fn signal_statistics(&self, tolerance: f32) -> BTreeMap<String, SignalStatistics> {
+        let logs = self.map_analysers(|analyser| analyser.signal_log(tolerance));
+        signal_statistics(logs.iter().flatten())
+    }
+
+    /// Overlays the speed over the distance of all routes in a single SVG image.
+    /// The routes should be runs of the same line, e.g. a partition by [route](crate::partition::route).
+    /// For more details see [overlay_chart].
+    ///
+    /// Errors will be propagated.
+    pub fn speed_chart(&self, options: &ChartOptions) -> Result<String, AnalyseError> {
+        overlay_chart(self.analysers.iter().map(|analyser| analyser.as_ref().result().as_ref()), options)
+    }
+
+    /// Writes a self-contained HTML page with the metrics of the group and all runs, their stops, overspeed periods
+    /// and the overlaid speed chart.
+    /// For more details see [write_group_html].
+    pub fn write_html<W: Write>(&self, options: &HtmlOptions, out: W) -> io::Result<()>
+    where
+        R: SourceFile,
+    {
+        write_group_html(self, options, out)
+    }
+
+    /// Collects the timetable stops of all routes into a single punctuality analysis.
+    /// Routes without timetable stops are skipped.
+    /// For more details see [punctuality](ResultAnalyser::punctuality).
+    ///
+    /// Throws [AnalyseError::NoTimetableEntries] if no route has any timetable stops, other errors will be propagated.
+    pub fn punctuality(&self, threshold: Duration) -> Result<Punctuality, AnalyseError> {
+        merge_punctuality(self.map_analysers(|analyser| analyser.punctuality(threshold)), threshold)
+    }
+}
+
+/// Parallel processing with `rayon`, which requires analysers that can be shared between threads.
+/// The results are identical to the sequential methods and keep the order of the analysers.
+#[cfg(feature = "parallel")]
+impl<A: AsRef<ResultAnalyser<R>> + Sync, R: AsRef<ZusiResult>> ResultAnalyserGroup<A, R> {
+    /// Applies `f` to all analysers in parallel and keeps their order.
+    pub fn par_map<'a, T: Send, F: Fn(&'a ResultAnalyser<R>) -> T + Sync + Send>(&'a self, f: F) -> Vec<T> {
+        use rayon::prelude::*;
+        self.analysers.par_iter().map(|analyser| f(analyser.as_ref())).collect()
+    }
+
+    /// Computes the [RunSummary] of all analysers in parallel.
+    /// The summaries are cached, so the metrics of the group which are aggregated from them only read the caches afterwards.
+    pub fn par_summaries(&self) -> Vec<&RunSummary> {
+        self.par_map(|analyser| analyser.summary())
+    }
+
+    /// Computes the standstills of all routes in parallel and shares the cache with [standstill_summary](Self::standstill_summary).
+    ///
+    /// Errors will be propagated.
+    pub fn par_standstill_summary(&self) -> Result<StandstillSummary, AnalyseError> {
+        *self.cache.standstill_summary.get_or_init(|| sum_standstills(self.par_map(|analyser| analyser.standstills())))
+    }
+
+    /// Analyses the punctuality of all routes in parallel, see [punctuality](Self::punctuality).
+    ///
+    /// Throws [AnalyseError::NoTimetableEntries] if no route has any timetable stops, other errors will be propagated.
+    pub fn par_punctuality(&self, threshold: Duration) -> Result<Punctuality, AnalyseError> {
+        merge_punctuality(self.par_map(|analyser| analyser.punctuality(threshold)), threshold)
+    }
+
+    /// Scores all routes in parallel, see [rank_by_score](Self::rank_by_score).
+    ///
+    /// Errors will be propagated.
+    pub fn par_rank_by_score(&self, options: &ScoreOptions) -> Result<Vec<(&A, Score)>, AnalyseError> {
+        rank(&self.analysers, self.par_map(|analyser| analyser.score(options)))
+    }
+
+    /// Analyses the train protection of all routes in parallel and shares the cache with [train_protection_summary](Self::train_protection_summary).
+    ///
+    /// Errors will be propagated.
+    pub fn par_train_protection_summary(&self) -> Result<TrainProtectionSummary, AnalyseError> {
+        *self.cache.train_protection_summary.get_or_init(|| sum_train_protection(self.par_map(|analyser| analyser.train_protection())))
+    }
+
+    /// Logs the signal passings of all routes in parallel, see [signal_statistics](Self::signal_statistics).
+    pub fn par_signal_statistics(&self, tolerance: f32) -> BTreeMap<String, SignalStatistics> {
+        let logs = self.par_map(|analyser| analyser.signal_log(tolerance));
+        signal_statistics(logs.iter().flatten())
+    }
+}
+
+/// Adds up the standstills of all routes, the first error in the order of the analysers is returned.
+fn sum_standstills(standstills: Vec<Result<Vec<Standstill>, AnalyseError>>) -> Result<StandstillSummary, AnalyseError> {
+    let mut standstill_summary = StandstillSummary::new();
+
+    for standstills in standstills {
+        standstill_summary += standstills?.iter().collect();
+    }
+
+    Ok(standstill_summary)
+}
+
+/// Adds up the train protection analyses of all routes, the first error in the order of the analysers is returned.
+fn sum_train_protection(train_protections: Vec<Result<TrainProtection, AnalyseError>>) -> Result<TrainProtectionSummary, AnalyseError> {
+    let mut train_protection_summary = TrainProtectionSummary::new();
+
+    for train_protection in train_protections {
+        train_protection_summary += (&train_protection?).into();
+    }
+
+    Ok(train_protection_summary)
+}
+
+/// Sorts the analysers by their scores from the highest to the lowest, keeping the order of equal scores.
+fn rank<A>(analysers: &[A], scores: Vec<Result<Score, AnalyseError>>) -> Result<Vec<(&A, Score)>, AnalyseError> {
+    let mut ranking = analysers.iter()
+        .zip(scores)
+        .map(|(analyser, score)| Ok((analyser, score?)))
+        .collect::<Result<Vec<(&A, Score)>, AnalyseError>>()?;
+    ranking.sort_by(|(_, a), (_, b)| b.total.total_cmp(&a.total));
+    Ok(ranking)
+}
+
+/// Collects the stops of all routes, skipping routes without timetable stops.
+fn merge_punctuality(punctualities: Vec<Result<Punctuality, AnalyseError>>, threshold: Duration) -> Result<Punctuality, AnalyseError> {
+    let mut stops = vec![];
+
+    for punctuality in punctualities {
+        match punctuality {
+            Ok(mut punctuality) => stops.append(&mut punctuality.stops),
+            Err(AnalyseError::NoTimetableEntries) => {}
+            Err(error) => return Err(error),
         }
+    }
 
-        self.cache.total_pure_driving_time = Some(total_pure_driving_time);
-        Ok(total_pure_driving_time)
+    if stops.is_empty() {
+        return Err(AnalyseError::NoTimetableEntries);
     }
+
+    Ok(Punctuality {
+        stops,
+        threshold,
+    })
 }
 
 impl<R: AsRef<ZusiResult>> TryFrom<Vec<R>> for ResultAnalyserGroup<ResultAnalyser<R>, R> {
@@ -1,24 +1,119 @@
+use std::ops::{Add, Sub};
+use std::sync::OnceLock;
+
 use time::Duration;
 
-#[derive(PartialEq, Debug)]
+use crate::result_analyser::{AnalyseError, RunSummary};
+use crate::standstill::StandstillSummary;
+use crate::statistics::{Distribution, RunMetric};
+use crate::train_protection::TrainProtectionSummary;
+
+/// Every value is computed at most once, errors are cached as well.
+/// [OnceLock] allows filling the cache through a shared reference and keeps the group [Sync].
+#[derive(Debug)]
 pub(super) struct AnalyserGroupCache {
-    pub(super) total_distance: Option<f32>,
-    pub(super) average_distance: Option<f32>,
-    pub(super) average_speed: Option<f32>,
-    pub(super) pure_average_speed: Option<f32>,
-    pub(super) total_driving_time: Option<Duration>,
-    pub(super) total_pure_driving_time: Option<Duration>,
+    pub(super) total_distance: OnceLock<Result<f32, AnalyseError>>,
+    pub(super) average_distance: OnceLock<Result<f32, AnalyseError>>,
+    pub(super) average_speed: OnceLock<Result<f32, AnalyseError>>,
+    pub(super) pure_average_speed: OnceLock<Result<f32, AnalyseError>>,
+    pub(super) total_driving_time: OnceLock<Result<Duration, AnalyseError>>,
+    pub(super) total_pure_driving_time: OnceLock<Result<Duration, AnalyseError>>,
+    pub(super) total_energy_consumption: OnceLock<f32>,
+    pub(super) average_energy_consumption_per_km: OnceLock<Result<f32, AnalyseError>>,
+    pub(super) standstill_summary: OnceLock<Result<StandstillSummary, AnalyseError>>,
+    pub(super) train_protection_summary: OnceLock<Result<TrainProtectionSummary, AnalyseError>>,
+    /// Indexed by the position of the metric in [RunMetric::ALL].
+    pub(super) distributions: [OnceLock<Result<Distribution, AnalyseError>>; RunMetric::ALL.len()],
+}
+
+/// The values a single analyser adds to the additive totals of a group.
+pub(super) struct Contribution {
+    distance: Result<f32, AnalyseError>,
+    driving_time: Result<Duration, AnalyseError>,
+    pure_driving_time: Result<Duration, AnalyseError>,
+    energy_consumption: f32,
+}
+
+impl Contribution {
+    pub fn new(summary: &RunSummary, energy_consumption: f32) -> Contribution {
+        Self {
+            distance: summary.distance(),
+            driving_time: summary.driving_time(),
+            pure_driving_time: summary.pure_driving_time(),
+            energy_consumption,
+        }
+    }
 }
 
 impl AnalyserGroupCache {
     pub fn new() -> AnalyserGroupCache {
         Self {
-            total_distance: None,
-            average_distance: None,
-            average_speed: None,
-            pure_average_speed: None,
-            total_driving_time: None,
-            total_pure_driving_time: None,
+            total_distance: OnceLock::new(),
+            average_distance: OnceLock::new(),
+            average_speed: OnceLock::new(),
+            pure_average_speed: OnceLock::new(),
+            total_driving_time: OnceLock::new(),
+            total_pure_driving_time: OnceLock::new(),
+            total_energy_consumption: OnceLock::new(),
+            average_energy_consumption_per_km: OnceLock::new(),
+            standstill_summary: OnceLock::new(),
+            train_protection_summary: OnceLock::new(),
+            distributions: Default::default(),
+        }
+    }
+
+    /// Updates the cache for an analyser appended to the group.
+    /// Cached totals are increased, all other values are invalidated.
+    pub fn add(&mut self, contribution: Contribution) {
+        add(&mut self.total_distance, contribution.distance);
+        add(&mut self.total_driving_time, contribution.driving_time);
+        add(&mut self.total_pure_driving_time, contribution.pure_driving_time);
+        if let Some(total) = self.total_energy_consumption.get_mut() {
+            *total += contribution.energy_consumption;
         }
+        self.invalidate_non_additive();
     }
-}
\ No newline at end of file
+
+    /// Updates the cache for an analyser removed from the group.
+    /// Cached totals are decreased, all other values are invalidated.
+    pub fn subtract(&mut self, contribution: Contribution) {
+        subtract(&mut self.total_distance, contribution.distance);
+        subtract(&mut self.total_driving_time, contribution.driving_time);
+        subtract(&mut self.total_pure_driving_time, contribution.pure_driving_time);
+        if let Some(total) = self.total_energy_consumption.get_mut() {
+            *total -= contribution.energy_consumption;
+        }
+        self.invalidate_non_additive();
+    }
+
+    fn invalidate_non_additive(&mut self) {
+        self.average_distance.take();
+        self.average_speed.take();
+        self.pure_average_speed.take();
+        self.average_energy_consumption_per_km.take();
+        self.standstill_summary.take();
+        self.train_protection_summary.take();
+        self.distributions.iter_mut().for_each(|distribution| {
+            distribution.take();
+        });
+    }
+}
+
+/// As analysers are appended, a cached error stays the first error of the group.
+fn add<T: Add<Output = T> + Copy>(total: &mut OnceLock<Result<T, AnalyseError>>, value: Result<T, AnalyseError>) {
+    match (total.get_mut(), value) {
+        (Some(Ok(total)), Ok(value)) => *total = *total + value,
+        (Some(Ok(_)), Err(error)) => *total = OnceLock::from(Err(error)),
+        _ => {}
+    }
+}
+
+/// If the cached total is an error, it can't be known whether the removed analyser caused it, so the total is invalidated.
+fn subtract<T: Sub<Output = T> + Copy>(total: &mut OnceLock<Result<T, AnalyseError>>, value: Result<T, AnalyseError>) {
+    match (total.get_mut(), value) {
+        (Some(Ok(total)), Ok(value)) => *total = *total - value,
+        _ => {
+            total.take();
+        }
+    }
+}
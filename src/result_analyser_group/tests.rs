@@ -1,3 +1,7 @@
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
 use time::Duration;
 use time::macros::datetime;
 use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
@@ -5,6 +9,7 @@ use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
 
 use crate::result_analyser::{AnalyseError, ResultAnalyser};
 use crate::result_analyser_group::{CreateAnalyserGroupError, ResultAnalyserGroup};
+use crate::statistics::RunMetric;
 
 #[test]
 fn test_caching() {
@@ -59,7 +64,7 @@ fn test_caching() {
         ])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -74,6 +79,73 @@ fn test_caching() {
     }
 }
 
+#[test]
+fn test_equality_ignores_cache() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2.33)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![ResultAnalyser::new(&result)]).unwrap();
+    let other = ResultAnalyserGroup::new(vec![ResultAnalyser::new(&result)]).unwrap();
+    analyser_group.total_distance().unwrap();
+    assert_eq!(analyser_group, other);
+}
+
+#[test]
+fn test_analysers_without_sync() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2.33)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(22.43)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .build()),
+        ])
+        .build();
+
+    let analyser = Rc::new(ResultAnalyser::new(result));
+    let analyser_group = ResultAnalyserGroup::new(vec![Rc::clone(&analyser), analyser]).unwrap();
+    assert_eq!(analyser_group.total_distance().unwrap(), 40.2);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel() {
+    let result = |fahrt_weg| ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(fahrt_weg)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new((1..=8).map(|index| ResultAnalyser::new(result(index as f32 * 100.))).collect()).unwrap();
+
+    let distances = analyser_group.par_map(|analyser| analyser.distance());
+    assert_eq!(distances, analyser_group.analysers().iter().map(|analyser| analyser.distance()).collect::<Vec<_>>());
+    assert_eq!(analyser_group.par_summaries().len(), 8);
+    assert_eq!(analyser_group.total_distance().unwrap(), 3600.);
+    assert_eq!(analyser_group.par_standstill_summary().unwrap().unscheduled_stops, 8);
+    assert_eq!(analyser_group.par_punctuality(Duration::minutes(1)), Err(AnalyseError::NoTimetableEntries));
+    assert_eq!(analyser_group.par_train_protection_summary().unwrap().forced_brakes, 0);
+}
+
 #[test]
 fn test_create_analyser_group_from_ref() {
     let analyser = ResultAnalyser::new(
@@ -122,7 +194,7 @@ fn test_total_distance() {
         ])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -150,7 +222,7 @@ fn test_total_distance_with_error() {
         .value(vec![])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -190,7 +262,7 @@ fn test_average_distance() {
         ])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -218,7 +290,7 @@ fn test_average_distance_with_error() {
         .value(vec![])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -262,7 +334,7 @@ fn test_average_speed_2() {
         ])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -303,7 +375,7 @@ fn test_average_speed_2_zero_driving_time() {
         ])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -344,7 +416,7 @@ fn test_pure_average_speed_2() {
         ])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -375,7 +447,7 @@ fn test_pure_average_speed_1() {
         ])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -394,7 +466,7 @@ fn test_pure_average_speed_0() {
         .value(vec![])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -427,7 +499,7 @@ fn test_total_driving_time() {
         ])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -453,7 +525,7 @@ fn test_total_driving_time_with_error() {
         .value(vec![])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -501,7 +573,7 @@ fn test_total_pure_driving_time() {
         ])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -527,7 +599,7 @@ fn test_total_pure_driving_time_with_error() {
         .value(vec![])
         .build();
 
-    let mut analyser_group = ResultAnalyserGroup::new(vec![
+    let analyser_group = ResultAnalyserGroup::new(vec![
         ResultAnalyser::new(result1),
         ResultAnalyser::new(result2),
     ]).unwrap();
@@ -555,4 +627,439 @@ fn test_try_from_zero_results() {
             as Result<ResultAnalyserGroup<ResultAnalyser<ZusiResult>, ZusiResult>, CreateAnalyserGroupError>,
         Err(CreateAnalyserGroupError::NoAnalysers)
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_punctuality() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(1998-01-30 9:21:00))
+                .fahrt_text("Kassel Hbf".into())
+                .fahrt_fpl_ank(Some(35825.390625))
+                .fahrt_fpl_abf(Some(35825.40625))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(1998-01-30 9:47:00))
+                .fahrt_speed(-1.)
+                .fahrt_text("Kassel Hbf".into())
+                .fahrt_parameter(1)
+                .build()),
+        ])
+        .build();
+    let late_result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(1998-01-30 9:30:00))
+                .fahrt_text("Kassel Hbf".into())
+                .fahrt_fpl_ank(Some(35825.390625))
+                .fahrt_fpl_abf(Some(35825.40625))
+                .build()),
+        ])
+        .build();
+
+    let free_run = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2.33)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result),
+        ResultAnalyser::new(free_run),
+        ResultAnalyser::new(late_result),
+    ]).unwrap();
+
+    let punctuality = analyser_group.punctuality(Duration::minutes(2)).unwrap();
+    assert_eq!(punctuality.stops.len(), 2);
+    assert_eq!(punctuality.punctual_stops(), 1);
+    assert_eq!(punctuality.punctual_share(), Some(0.5));
+    assert_eq!(punctuality.max_delay(), Some(Duration::seconds(450)));
+}
+
+#[test]
+fn test_punctuality_with_error() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.punctuality(Duration::minutes(2)), Err(AnalyseError::NoTimetableEntries));
+}
+
+#[test]
+fn test_standstill_summary() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_text("Hofgeismar".into())
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:22))
+                .fahrt_speed(8.)
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:21))
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    for _ in 0..2 {
+        let summary = analyser_group.standstill_summary().unwrap();
+        assert_eq!(summary.scheduled_stops, 1);
+        assert_eq!(summary.scheduled_dwell_time, Duration::minutes(2));
+        assert_eq!(summary.unscheduled_stops, 1);
+        assert_eq!(summary.average_unscheduled_stop_time(), Some(Duration::minutes(3)));
+    }
+}
+
+#[test]
+fn test_energy_consumption() {
+    let result1 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(36_000_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(1000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .build()),
+        ])
+        .build();
+    let result2 = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(36_000_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(4000.)
+                .fahrt_zeit(datetime!(2019-01-01 23:28))
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result1),
+        ResultAnalyser::new(result2),
+    ]).unwrap();
+
+    for _ in 0..2 {
+        assert_eq!(analyser_group.total_energy_consumption(), 20.);
+        assert_eq!(analyser_group.average_energy_consumption_per_km().unwrap(), 4.);
+    }
+    let (most_efficient, consumption) = analyser_group.most_energy_efficient().unwrap();
+    assert_eq!(most_efficient.distance().unwrap(), 4000.);
+    assert_eq!(consumption, 2.5);
+    let (least_efficient, consumption) = analyser_group.least_energy_efficient().unwrap();
+    assert_eq!(least_efficient.distance().unwrap(), 1000.);
+    assert_eq!(consumption, 10.);
+}
+
+#[test]
+fn test_shared_across_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ResultAnalyserGroup<ResultAnalyser<ZusiResult>, ZusiResult>>();
+
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2.33)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(22.43)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = Arc::new(ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result),
+    ]).unwrap());
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let analyser_group = Arc::clone(&analyser_group);
+            thread::spawn(move || analyser_group.total_distance())
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap().unwrap(), 20.1);
+    }
+    let total_distance = analyser_group.total_distance();
+    let average_distance = analyser_group.average_distance();
+    assert_eq!(total_distance, average_distance);
+}
+
+#[test]
+fn test_distribution() {
+    let results: Vec<ZusiResult> = [10., 40., 20.].into_iter()
+        .map(|fahrt_weg| ZusiResult::builder()
+            .datum(datetime!(2019-01-01 23:14))
+            .value(vec![
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_weg(0.)
+                    .fahrt_zeit(datetime!(2019-01-01 23:18))
+                    .build()),
+                ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                    .fahrt_weg(fahrt_weg)
+                    .fahrt_zeit(datetime!(2019-01-01 23:19))
+                    .build()),
+            ])
+            .build())
+        .collect();
+
+    let analyser_group: ResultAnalyserGroup<ResultAnalyser<ZusiResult>, ZusiResult> = results.try_into().unwrap();
+
+    for _ in 0..2 {
+        let distribution = analyser_group.distribution(RunMetric::Distance).unwrap();
+        assert_eq!(distribution.max().index, 1);
+        assert_eq!(analyser_group.analysers()[distribution.max().index].distance().unwrap(), 40.);
+        assert_eq!(distribution.min().value, 10.);
+        assert_eq!(distribution.median(), 20.);
+    }
+    let distribution = analyser_group.distribution(RunMetric::AverageSpeed).unwrap();
+    assert_eq!(distribution.max().value, 40. / 60.);
+    let distribution = analyser_group.distribution(RunMetric::DrivingTime).unwrap();
+    assert_eq!(distribution.standard_deviation(), 0.);
+}
+
+#[test]
+fn test_distribution_with_error() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result),
+    ]).unwrap();
+
+    assert_eq!(analyser_group.distribution(RunMetric::PureAverageSpeed), Err(AnalyseError::NoEntries));
+}
+
+fn run(fahrt_weg: f32, minutes: u8) -> ResultAnalyser<ZusiResult> {
+    ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .verbrauch(3_600_000.)
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(0.)
+                .fahrt_zeit(datetime!(2019-01-01 23:00))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(fahrt_weg)
+                .fahrt_zeit(datetime!(2019-01-01 23:00) + Duration::minutes(minutes as i64))
+                .build()),
+        ])
+        .build())
+}
+
+#[test]
+fn test_push_updates_cache() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![run(1000., 1)]).unwrap();
+
+    assert_eq!(analyser_group.total_distance(), Ok(1000.));
+    assert_eq!(analyser_group.average_distance(), Ok(1000.));
+    assert_eq!(analyser_group.total_driving_time(), Ok(Duration::minutes(1)));
+    assert_eq!(analyser_group.total_energy_consumption(), 1.);
+
+    analyser_group.push(run(3000., 2));
+    assert_eq!(analyser_group.total_distance(), Ok(4000.));
+    assert_eq!(analyser_group.average_distance(), Ok(2000.));
+    assert_eq!(analyser_group.total_driving_time(), Ok(Duration::minutes(3)));
+    assert_eq!(analyser_group.total_energy_consumption(), 2.);
+
+    analyser_group.extend(vec![run(2000., 1), run(2000., 1)]);
+    assert_eq!(analyser_group.analysers().len(), 4);
+    assert_eq!(analyser_group.total_distance(), Ok(8000.));
+    assert_eq!(analyser_group.average_distance(), Ok(2000.));
+    assert_eq!(analyser_group.distribution(RunMetric::Distance).unwrap().count(), 4);
+}
+
+#[test]
+fn test_push_with_error() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![run(1000., 1)]).unwrap();
+    assert_eq!(analyser_group.total_distance(), Ok(1000.));
+
+    analyser_group.push(ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build()));
+    assert_eq!(analyser_group.total_distance(), Err(AnalyseError::NoEntries));
+
+    analyser_group.remove(1).unwrap();
+    assert_eq!(analyser_group.total_distance(), Ok(1000.));
+}
+
+#[test]
+fn test_remove() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![run(1000., 1), run(3000., 2)]).unwrap();
+    assert_eq!(analyser_group.total_distance(), Ok(4000.));
+    assert_eq!(analyser_group.average_distance(), Ok(2000.));
+
+    let analyser = analyser_group.remove(0).unwrap();
+    assert_eq!(analyser.distance(), Ok(1000.));
+    assert_eq!(analyser_group.total_distance(), Ok(3000.));
+    assert_eq!(analyser_group.average_distance(), Ok(3000.));
+    assert_eq!(analyser_group.total_driving_time(), Ok(Duration::minutes(2)));
+
+    assert_eq!(analyser_group.remove(0), Err(CreateAnalyserGroupError::NoAnalysers));
+    assert_eq!(analyser_group.analysers().len(), 1);
+}
+
+#[test]
+fn test_retain() {
+    let mut analyser_group = ResultAnalyserGroup::new(vec![run(1000., 1), run(3000., 2), run(5000., 3)]).unwrap();
+    assert_eq!(analyser_group.total_distance(), Ok(9000.));
+    assert_eq!(analyser_group.total_energy_consumption(), 3.);
+
+    assert_eq!(analyser_group.retain(|analyser| analyser.distance().unwrap() > 10000.), Err(CreateAnalyserGroupError::NoAnalysers));
+    assert_eq!(analyser_group.analysers().len(), 3);
+
+    analyser_group.retain(|analyser| analyser.distance().unwrap() > 2000.).unwrap();
+    assert_eq!(analyser_group.analysers().len(), 2);
+    assert_eq!(analyser_group.total_distance(), Ok(8000.));
+    assert_eq!(analyser_group.average_distance(), Ok(4000.));
+    assert_eq!(analyser_group.total_driving_time(), Ok(Duration::minutes(5)));
+    assert_eq!(analyser_group.total_energy_consumption(), 2.);
+}
+
+#[test]
+fn test_error_order_with_many_analysers() {
+    let mut analysers: Vec<ResultAnalyser<ZusiResult>> = (0..64).map(|i| run(1000. + i as f32, 1)).collect();
+    analysers[10] = run(1000., 0);
+    analysers[40] = ResultAnalyser::new(ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![])
+        .build());
+
+    let analyser_group = ResultAnalyserGroup::new(analysers).unwrap();
+
+    assert_eq!(analyser_group.average_speed(), Err(AnalyseError::ZeroDrivingTime));
+    assert_eq!(analyser_group.total_distance(), Err(AnalyseError::NoEntries));
+    assert_eq!(analyser_group.distribution(RunMetric::AverageSpeed), Err(AnalyseError::ZeroDrivingTime));
+}
+
+#[test]
+fn test_speed_chart() {
+    let analyser_group = ResultAnalyserGroup::new(vec![run(1000., 1), run(2000., 2)]).unwrap();
+    let svg = analyser_group.speed_chart(&Default::default()).unwrap();
+    assert_eq!(svg.matches(" 2019-01-01</text>").count(), 2);
+}
+
+#[test]
+fn test_train_protection_summary() {
+    let result = |fahrt_parameter| ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(8)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_parameter(fahrt_parameter)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .build()),
+        ])
+        .build();
+
+    let mut analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result(48)),
+        ResultAnalyser::new(result(192)),
+    ]).unwrap();
+
+    let summary = analyser_group.train_protection_summary().unwrap();
+    assert_eq!(summary.influences, 1);
+    assert_eq!(summary.forced_brakes, 1);
+    assert_eq!(summary.restrictive_supervisions, 1);
+    assert_eq!(summary.restrictive_supervision_time, Duration::minutes(1));
+
+    analyser_group.push(ResultAnalyser::new(result(192)));
+    assert_eq!(analyser_group.train_protection_summary().unwrap().forced_brakes, 2);
+}
+
+#[test]
+fn test_signal_statistics() {
+    let result = |fahrt_speed| ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .fahrt_speed_signal(16.6667)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(5)
+                .fahrt_weg(100.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(fahrt_speed)
+                .fahrt_text("Asig A".to_owned())
+                .build()),
+        ])
+        .build();
+
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        ResultAnalyser::new(result(10.)),
+        ResultAnalyser::new(result(20.)),
+    ]).unwrap();
+
+    let statistics = analyser_group.signal_statistics(0.);
+    assert_eq!(statistics.len(), 1);
+    let signal = statistics["Asig A"];
+    assert_eq!(signal.passings, 2);
+    assert_eq!(signal.mean_speed, 15.);
+    assert_eq!(signal.max_speed, 20.);
+    assert_eq!(signal.above_limit, 1);
+
+    #[cfg(feature = "parallel")]
+    assert_eq!(analyser_group.par_signal_statistics(0.), statistics);
+}
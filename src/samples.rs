@@ -0,0 +1,67 @@
+use std::slice::Iter;
+
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+#[cfg(test)]
+mod tests;
+
+/// Value Zusi writes for attributes which are not set, e.g. all measurement attributes of an event-only entry
+/// or a speed limit which is not active.
+pub const NO_VALUE: f32 = -1.;
+
+/// Checks whether a [FahrtEintrag] is a measurement sample.
+/// Event-only entries have their `fahrt_weg` attribute set to [NO_VALUE] and do not describe the train's position or speed.
+pub fn is_measurement(fahrt_eintrag: &FahrtEintrag) -> bool {
+    fahrt_eintrag.fahrt_weg != NO_VALUE
+}
+
+/// A view over all measurement samples of a [ZusiResult].
+/// Event-only [FahrtEintrag] entries are skipped, see [is_measurement].
+#[derive(PartialEq, Debug, Clone)]
+pub struct Samples<'a> {
+    entries: Vec<&'a FahrtEintrag>,
+}
+
+impl<'a> Samples<'a> {
+    pub fn new(result: &'a ZusiResult) -> Samples<'a> {
+        Self {
+            entries: result.value.iter()
+                .map(|value| {
+                    let ResultValue::FahrtEintrag(fahrt_eintrag) = value;
+                    fahrt_eintrag
+                })
+                .filter(|fahrt_eintrag| is_measurement(fahrt_eintrag))
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a FahrtEintrag> {
+        self.entries.get(index).copied()
+    }
+
+    pub fn first(&self) -> Option<&'a FahrtEintrag> {
+        self.entries.first().copied()
+    }
+
+    pub fn last(&self) -> Option<&'a FahrtEintrag> {
+        self.entries.last().copied()
+    }
+
+    pub fn iter(&self) -> std::iter::Copied<Iter<'_, &'a FahrtEintrag>> {
+        self.entries.iter().copied()
+    }
+
+    /// Iterates over all pairs of consecutive samples.
+    pub fn pairs(&self) -> impl Iterator<Item = (&'a FahrtEintrag, &'a FahrtEintrag)> + '_ {
+        self.entries.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+}
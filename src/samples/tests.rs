@@ -0,0 +1,66 @@
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::samples::Samples;
+
+#[test]
+fn test_samples_skip_event_entries() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(8)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:17))
+                .fahrt_speed(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2.33)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(2)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:19))
+                .fahrt_speed(-1.)
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(22.43)
+                .fahrt_zeit(datetime!(2019-01-01 23:20))
+                .build()),
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(4)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:21))
+                .fahrt_speed(-1.)
+                .build()),
+        ])
+        .build();
+
+    let samples = Samples::new(&result);
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples.first().unwrap().fahrt_weg, 2.33);
+    assert_eq!(samples.last().unwrap().fahrt_weg, 22.43);
+    assert_eq!(samples.pairs().count(), 1);
+}
+
+#[test]
+fn test_samples_only_event_entries() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_typ(8)
+                .fahrt_weg(-1.)
+                .fahrt_zeit(datetime!(2019-01-01 23:17))
+                .fahrt_speed(-1.)
+                .build()),
+        ])
+        .build();
+
+    let samples = Samples::new(&result);
+    assert!(samples.is_empty());
+    assert_eq!(samples.first(), None);
+    assert_eq!(samples.pairs().count(), 0);
+}
@@ -0,0 +1,229 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use time::Duration;
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+use crate::kinematics::KinematicsOptions;
+use crate::result_analyser::{AnalyseError, ResultAnalyser};
+use crate::standstill::StandstillSummary;
+
+#[cfg(test)]
+mod tests;
+
+/// Highest score of the total and of every sub-score.
+pub const MAX_SCORE: f32 = 100.;
+
+/// A part of the [Score] together with the metric it is derived from and its formula.
+/// Shares are given as values between 0 and 1.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Criterion {
+    /// Metric: share of punctual timetable stops, see [punctuality](ResultAnalyser::punctuality).
+    ///
+    /// `score = 100 × share`
+    ///
+    /// Not scored if the run has no timetable stops with a known delay.
+    Punctuality,
+    /// Metric: share of the pure driving time above the effective speed limit,
+    /// see [speed_limit_compliance](ResultAnalyser::speed_limit_compliance).
+    ///
+    /// `score = 100 × (1 − min(1, share / max_overspeed_share))`
+    SpeedLimit,
+    /// Metric: share of the pure driving time above the comfort acceleration or deceleration,
+    /// see [kinematics](ResultAnalyser::kinematics).
+    ///
+    /// `score = 100 × (1 − min(1, share / max_harsh_share))`
+    Smoothness,
+    /// Metric: number of unscheduled stops, see [standstills](ResultAnalyser::standstills).
+    ///
+    /// `score = max(0, 100 − count × unscheduled_stop_penalty)`
+    UnscheduledStops,
+    /// Metric: energy consumption in kWh/km, see [energy_consumption_per_km](ResultAnalyser::energy_consumption_per_km).
+    ///
+    /// `score = 100 × clamp(2 − consumption / energy_target, 0, 1)`,
+    /// so the full score is reached up to the target and no score from twice the target.
+    ///
+    /// Not scored if no [energy target](ScoreOptions::energy_target) is set.
+    Energy,
+}
+
+/// Weight of each [Criterion] in the total score, only the ratio between the weights matters.
+/// Weights must not be negative and at least one weight must be positive.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct ScoreWeights {
+    pub punctuality: f32,
+    pub speed_limit: f32,
+    pub smoothness: f32,
+    pub unscheduled_stops: f32,
+    pub energy: f32,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            punctuality: 1.,
+            speed_limit: 1.,
+            smoothness: 1.,
+            unscheduled_stops: 1.,
+            energy: 1.,
+        }
+    }
+}
+
+impl ScoreWeights {
+    pub fn weight(&self, criterion: Criterion) -> f32 {
+        match criterion {
+            Criterion::Punctuality => self.punctuality,
+            Criterion::SpeedLimit => self.speed_limit,
+            Criterion::Smoothness => self.smoothness,
+            Criterion::UnscheduledStops => self.unscheduled_stops,
+            Criterion::Energy => self.energy,
+        }
+    }
+}
+
+/// Weights and thresholds of the formulas documented at [Criterion].
+#[derive(PartialEq, Debug, Clone)]
+pub struct ScoreOptions {
+    pub weights: ScoreWeights,
+    /// Stops with a delay up to this threshold count as punctual.
+    pub punctuality_threshold: Duration,
+    /// Speed above the effective limit which is not counted as overspeed, in m/s.
+    pub speed_limit_tolerance: f32,
+    /// Share of the pure driving time above the speed limit resulting in no score.
+    pub max_overspeed_share: f32,
+    pub kinematics: KinematicsOptions,
+    /// Share of the pure driving time above the comfort acceleration or deceleration resulting in no score.
+    pub max_harsh_share: f32,
+    /// Points deducted per unscheduled stop, must not be negative.
+    pub unscheduled_stop_penalty: f32,
+    /// Energy consumption in kWh/km which still gets the full score, must be positive.
+    /// The consumption depends on the train, so there is no default and energy is not scored without a target.
+    pub energy_target: Option<f32>,
+}
+
+impl Default for ScoreOptions {
+    fn default() -> Self {
+        Self {
+            weights: ScoreWeights::default(),
+            punctuality_threshold: Duration::minutes(5),
+            speed_limit_tolerance: 0.,
+            max_overspeed_share: 0.05,
+            kinematics: KinematicsOptions::default(),
+            max_harsh_share: 0.05,
+            unscheduled_stop_penalty: 25.,
+            energy_target: None,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SubScore {
+    pub criterion: Criterion,
+    /// Value between 0 and [MAX_SCORE].
+    pub score: f32,
+    pub weight: f32,
+    /// The metric the score is derived from, see [Criterion] for its unit.
+    pub metric: f32,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Score {
+    /// Weighted mean of all sub-scores between 0 and [MAX_SCORE], 0 if all scored criteria have a weight of zero.
+    pub total: f32,
+    /// All criteria which could be scored in the order of [Criterion].
+    pub breakdown: Vec<SubScore>,
+}
+
+impl Score {
+    pub fn sub_score(&self, criterion: Criterion) -> Option<&SubScore> {
+        self.breakdown.iter().find(|sub_score| sub_score.criterion == criterion)
+    }
+}
+
+/// Grades a run by combining the sub-scores of all [Criterion]s, see there for the formulas.
+///
+/// Throws [AnalyseError::InvalidOptions] if the energy target is not positive, the penalty or a weight is negative or all weights are zero,
+/// [AnalyseError::NoEntries] if the [ZusiResult] does not contain any measurement samples,
+/// [AnalyseError::ZeroDrivingTime] if the train did not move
+/// and [AnalyseError::ZeroDistance] if an energy target is set but the distance is zero.
+pub fn score<R: AsRef<ZusiResult>>(analyser: &ResultAnalyser<R>, options: &ScoreOptions) -> Result<Score, AnalyseError> {
+    validate(options)?;
+
+    let pure_driving_time = analyser.pure_driving_time()?;
+    if pure_driving_time.is_zero() {
+        return Err(AnalyseError::ZeroDrivingTime);
+    }
+
+    let mut metrics = vec![];
+
+    if let Some(share) = analyser.punctuality(options.punctuality_threshold).ok().and_then(|punctuality| punctuality.punctual_share()) {
+        metrics.push((Criterion::Punctuality, share, MAX_SCORE * share));
+    }
+
+    let compliance = analyser.speed_limit_compliance(options.speed_limit_tolerance)?;
+    let overspeed_share = compliance.time_above_limit / pure_driving_time;
+    metrics.push((Criterion::SpeedLimit, overspeed_share as f32, share_score(overspeed_share as f32, options.max_overspeed_share)));
+
+    let kinematics = analyser.kinematics(&options.kinematics)?;
+    let harsh_share = (kinematics.time_above_comfort_acceleration + kinematics.time_above_comfort_deceleration) / pure_driving_time;
+    metrics.push((Criterion::Smoothness, harsh_share as f32, share_score(harsh_share as f32, options.max_harsh_share)));
+
+    let unscheduled_stops = StandstillSummary::from_iter(analyser.standstills()?.iter()).unscheduled_stops as f32;
+    metrics.push((
+        Criterion::UnscheduledStops,
+        unscheduled_stops,
+        (MAX_SCORE - unscheduled_stops * options.unscheduled_stop_penalty).max(0.),
+    ));
+
+    if let Some(energy_target) = options.energy_target {
+        let consumption = analyser.energy_consumption_per_km()?;
+        metrics.push((Criterion::Energy, consumption, MAX_SCORE * (2. - consumption / energy_target).clamp(0., 1.)));
+    }
+
+    let breakdown: Vec<SubScore> = metrics.into_iter()
+        .map(|(criterion, metric, score)| SubScore {
+            criterion,
+            score,
+            weight: options.weights.weight(criterion),
+            metric,
+        })
+        .collect();
+
+    let total_weight: f32 = breakdown.iter().map(|sub_score| sub_score.weight).sum();
+    let total = if total_weight > 0. {
+        breakdown.iter().map(|sub_score| sub_score.score * sub_score.weight).sum::<f32>() / total_weight
+    } else {
+        0.
+    };
+
+    Ok(Score {
+        total,
+        breakdown,
+    })
+}
+
+fn validate(options: &ScoreOptions) -> Result<(), AnalyseError> {
+    let weights = options.weights;
+    let weights = [weights.punctuality, weights.speed_limit, weights.smoothness, weights.unscheduled_stops, weights.energy];
+    let invalid_weights = weights.iter().any(|weight| !weight.is_finite() || *weight < 0.)
+        || weights.iter().sum::<f32>() == 0.;
+    let invalid_penalty = !options.unscheduled_stop_penalty.is_finite() || options.unscheduled_stop_penalty < 0.;
+    let invalid_energy_target = options.energy_target.is_some_and(|energy_target| energy_target.is_nan() || energy_target <= 0.);
+
+    if invalid_weights || invalid_penalty || invalid_energy_target {
+        Err(AnalyseError::InvalidOptions)
+    } else {
+        Ok(())
+    }
+}
+
+/// Decreases linearly from the full score for a share of 0 to no score at `max_share`.
+fn share_score(share: f32, max_share: f32) -> f32 {
+    if max_share <= 0. {
+        return if share > 0. { 0. } else { MAX_SCORE };
+    }
+    MAX_SCORE * (1. - (share / max_share).min(1.))
+}
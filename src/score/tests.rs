@@ -0,0 +1,209 @@
+use zusi_xml_lib::xml::zusi::result::ZusiResult;
+
+use crate::fixtures::{run, sample};
+use crate::result_analyser::{AnalyseError, ResultAnalyser};
+use crate::result_analyser_group::ResultAnalyserGroup;
+use crate::score::{Criterion, ScoreOptions, ScoreWeights, share_score};
+use crate::speed_limit::LimitSource;
+
+/// Constant speed of 10 m/s for 200 m.
+fn constant_run(limit: f32) -> ResultAnalyser<ZusiResult> {
+    run(vec![
+        sample(0, 0., 10., Some((LimitSource::Track, limit))),
+        sample(10, 100., 10., Some((LimitSource::Track, limit))),
+        sample(20, 200., 10., Some((LimitSource::Track, limit))),
+    ])
+}
+
+#[test]
+fn test_score_perfect_run() {
+    let score = constant_run(20.).score(&ScoreOptions::default()).unwrap();
+
+    assert_eq!(score.total, 100.);
+    let criteria: Vec<Criterion> = score.breakdown.iter().map(|sub_score| sub_score.criterion).collect();
+    assert_eq!(criteria, vec![Criterion::SpeedLimit, Criterion::Smoothness, Criterion::UnscheduledStops]);
+    assert!(score.sub_score(Criterion::Punctuality).is_none());
+    assert!(score.sub_score(Criterion::Energy).is_none());
+}
+
+#[test]
+fn test_score_overspeed() {
+    let score = constant_run(5.).score(&ScoreOptions::default()).unwrap();
+
+    let speed_limit = score.sub_score(Criterion::SpeedLimit).unwrap();
+    assert_eq!(speed_limit.metric, 1.);
+    assert_eq!(speed_limit.score, 0.);
+    assert_eq!(speed_limit.weight, 1.);
+    assert_eq!(score.total, 200. / 3.);
+}
+
+#[test]
+fn test_score_weights() {
+    let options = ScoreOptions {
+        weights: ScoreWeights {
+            speed_limit: 0.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(constant_run(5.).score(&options).unwrap().total, 100.);
+
+    let options = ScoreOptions {
+        weights: ScoreWeights {
+            speed_limit: 2.,
+            smoothness: 0.,
+            unscheduled_stops: 2.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(constant_run(5.).score(&options).unwrap().total, 50.);
+
+    let options = ScoreOptions {
+        weights: ScoreWeights {
+            punctuality: 0.,
+            speed_limit: 0.,
+            smoothness: 0.,
+            unscheduled_stops: 0.,
+            energy: 1.,
+        },
+        ..Default::default()
+    };
+    let score = constant_run(5.).score(&options).unwrap();
+    assert_eq!(score.total, 0.);
+    assert_eq!(score.breakdown.len(), 3);
+}
+
+#[test]
+fn test_score_unscheduled_stops() {
+    let analyser = run(vec![
+        sample(0, 0., 10., Some((LimitSource::Track, 20.))),
+        sample(10, 100., 0., Some((LimitSource::Track, 20.))),
+        sample(20, 100., 0., Some((LimitSource::Track, 20.))),
+        sample(30, 150., 10., Some((LimitSource::Track, 20.))),
+    ]);
+    let options = ScoreOptions {
+        // accelerating and braking at 1 m/s² is not scored here
+        max_harsh_share: 1.,
+        unscheduled_stop_penalty: 30.,
+        ..Default::default()
+    };
+
+    let score = analyser.score(&options).unwrap();
+    let unscheduled_stops = score.sub_score(Criterion::UnscheduledStops).unwrap();
+    assert_eq!(unscheduled_stops.metric, 1.);
+    assert_eq!(unscheduled_stops.score, 70.);
+}
+
+#[test]
+fn test_score_energy() {
+    // 1 kWh for 200 m
+    let score = |energy_target| constant_run(20.)
+        .score(&ScoreOptions {
+            energy_target: Some(energy_target),
+            ..Default::default()
+        })
+        .unwrap()
+        .sub_score(Criterion::Energy)
+        .copied()
+        .unwrap();
+
+    assert_eq!(score(5.).metric, 5.);
+    assert_eq!(score(10.).score, 100.);
+    assert_eq!(score(5.).score, 100.);
+    assert_eq!(score(4.).score, 75.);
+    assert_eq!(score(2.).score, 0.);
+}
+
+#[test]
+fn test_score_errors() {
+    assert_eq!(run(vec![]).score(&ScoreOptions::default()), Err(AnalyseError::NoEntries));
+
+    let standing = run(vec![
+        sample(0, 0., 0., Some((LimitSource::Track, 20.))),
+        sample(10, 0., 0., Some((LimitSource::Track, 20.))),
+    ]);
+    assert_eq!(standing.score(&ScoreOptions::default()), Err(AnalyseError::ZeroDrivingTime));
+
+    for energy_target in [0., -1., f32::NAN] {
+        let options = ScoreOptions {
+            energy_target: Some(energy_target),
+            ..Default::default()
+        };
+        assert_eq!(constant_run(20.).score(&options), Err(AnalyseError::InvalidOptions));
+    }
+}
+
+#[test]
+fn test_score_invalid_penalty() {
+    for unscheduled_stop_penalty in [-1., f32::NAN, f32::INFINITY] {
+        let options = ScoreOptions {
+            unscheduled_stop_penalty,
+            ..Default::default()
+        };
+        assert_eq!(constant_run(20.).score(&options), Err(AnalyseError::InvalidOptions));
+    }
+}
+
+#[test]
+fn test_score_invalid_weights() {
+    let options = ScoreOptions {
+        weights: ScoreWeights {
+            smoothness: -1.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(constant_run(20.).score(&options), Err(AnalyseError::InvalidOptions));
+
+    let options = ScoreOptions {
+        weights: ScoreWeights {
+            energy: f32::NAN,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(constant_run(20.).score(&options), Err(AnalyseError::InvalidOptions));
+}
+
+#[test]
+fn test_score_zero_total_weight() {
+    let options = ScoreOptions {
+        weights: ScoreWeights {
+            punctuality: 0.,
+            speed_limit: 0.,
+            smoothness: 0.,
+            unscheduled_stops: 0.,
+            energy: 0.,
+        },
+        ..Default::default()
+    };
+    assert_eq!(constant_run(20.).score(&options), Err(AnalyseError::InvalidOptions));
+}
+
+#[test]
+fn test_share_score() {
+    assert_eq!(share_score(0., 0.1), 100.);
+    assert_eq!(share_score(0.05, 0.1), 50.);
+    assert_eq!(share_score(0.2, 0.1), 0.);
+    assert_eq!(share_score(0., 0.), 100.);
+    assert_eq!(share_score(0.01, 0.), 0.);
+}
+
+#[test]
+fn test_rank_by_score() {
+    let analyser_group = ResultAnalyserGroup::new(vec![
+        constant_run(5.),
+        constant_run(20.),
+        constant_run(5.),
+    ]).unwrap();
+
+    let ranking = analyser_group.rank_by_score(&ScoreOptions::default()).unwrap();
+    let totals: Vec<f32> = ranking.iter().map(|(_, score)| score.total).collect();
+    assert_eq!(totals, vec![100., 200. / 3., 200. / 3.]);
+    assert!(std::ptr::eq(ranking[0].0, &analyser_group.analysers()[1]));
+    assert!(std::ptr::eq(ranking[1].0, &analyser_group.analysers()[0]));
+
+    #[cfg(feature = "parallel")]
+    assert_eq!(analyser_group.par_rank_by_score(&ScoreOptions::default()).unwrap(), ranking);
+}
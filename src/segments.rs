@@ -0,0 +1,105 @@
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+
+use crate::events::{Event, EventKind};
+use crate::result_analyser::ResultAnalyser;
+use crate::samples::is_measurement;
+use crate::timetable::datetime_from_delphi;
+
+#[cfg(test)]
+mod tests;
+
+/// The part of a run between the departure at one timetable stop and the arrival at the next one.
+/// It starts with the last measurement sample before the departure, so the distance covers the whole way between both stops.
+///
+/// A segment contains a copy of the corresponding entries as a [ZusiResult],
+/// so it can be analysed by a [ResultAnalyser] and compared across runs by a [ResultAnalyserGroup](crate::result_analyser_group::ResultAnalyserGroup).
+#[derive(PartialEq, Debug, Clone)]
+pub struct Segment {
+    pub from: String,
+    pub to: String,
+    pub scheduled_departure: Option<PrimitiveDateTime>,
+    pub scheduled_arrival: Option<PrimitiveDateTime>,
+    result: ZusiResult,
+}
+
+impl Segment {
+    /// Computes the scheduled running time between departure and arrival.
+    ///
+    /// Returns [None] if the timetable does not contain both times.
+    pub fn scheduled_running_time(&self) -> Option<Duration> {
+        Some(self.scheduled_arrival? - self.scheduled_departure?)
+    }
+}
+
+impl AsRef<ZusiResult> for Segment {
+    fn as_ref(&self) -> &ZusiResult {
+        &self.result
+    }
+}
+
+struct Stop<'a> {
+    station: &'a str,
+    arrival_index: usize,
+    /// Index of the last measurement sample before the departure.
+    departure_index: Option<usize>,
+    scheduled_arrival: Option<f64>,
+    scheduled_departure: Option<f64>,
+}
+
+/// Splits a [ZusiResult] into segments at its timetable stops.
+/// Repeated arrivals at the same station before departing are merged into the first one
+/// and timetable rows without a station are skipped, see [EventKind::is_timetable_stop].
+/// A run with less than two timetable stops results in no segments.
+pub fn segments(result: &ZusiResult) -> Vec<ResultAnalyser<Segment>> {
+    let mut stops: Vec<Stop> = vec![];
+    let mut last_sample_index = 0;
+
+    for (index, value) in result.value.iter().enumerate() {
+        let ResultValue::FahrtEintrag(fahrt_eintrag) = value;
+        if is_measurement(fahrt_eintrag) {
+            last_sample_index = index;
+        }
+        match Event::from_fahrt_eintrag(fahrt_eintrag).map(|event| event.kind).filter(EventKind::is_timetable_stop) {
+            Some(EventKind::TimetableArrival { station }) => {
+                let already_arrived = stops.last()
+                    .is_some_and(|stop| stop.station == station && stop.departure_index.is_none());
+                if !already_arrived {
+                    stops.push(Stop {
+                        station,
+                        arrival_index: index,
+                        departure_index: None,
+                        scheduled_arrival: fahrt_eintrag.fahrt_fpl_ank,
+                        scheduled_departure: fahrt_eintrag.fahrt_fpl_abf,
+                    });
+                }
+            }
+            Some(EventKind::TimetableDeparture { station }) => {
+                if let Some(stop) = stops.last_mut().filter(|stop| stop.station == station) {
+                    stop.departure_index = Some(last_sample_index.max(stop.arrival_index));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    stops.windows(2)
+        .map(|pair| {
+            let (from, to) = (&pair[0], &pair[1]);
+            let start = from.departure_index.unwrap_or(from.arrival_index);
+            ResultAnalyser::new(Segment {
+                from: from.station.into(),
+                to: to.station.into(),
+                scheduled_departure: from.scheduled_departure.map(datetime_from_delphi),
+                scheduled_arrival: to.scheduled_arrival.map(datetime_from_delphi),
+                result: ZusiResult::builder()
+                    .zugnummer(result.zugnummer.clone())
+                    .tf_nummer(result.tf_nummer.clone())
+                    .datum(result.datum)
+                    .verbrauch(result.verbrauch)
+                    .value(result.value[start..=to.arrival_index].to_vec())
+                    .build(),
+            })
+        })
+        .collect()
+}
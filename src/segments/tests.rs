@@ -0,0 +1,115 @@
+use time::Duration;
+use time::macros::datetime;
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::result_analyser::ResultAnalyser;
+use crate::result_analyser_group::ResultAnalyserGroup;
+
+fn arrival(station: &str, fahrt_weg: f32, fahrt_zeit: PrimitiveDateTime, fahrt_fpl: f64) -> ResultValue {
+    ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_typ(2)
+        .fahrt_weg(fahrt_weg)
+        .fahrt_zeit(fahrt_zeit)
+        .fahrt_text(station.into())
+        .fahrt_fpl_ank(Some(fahrt_fpl))
+        .fahrt_fpl_abf(Some(fahrt_fpl))
+        .build())
+}
+
+fn departure(station: &str, fahrt_zeit: PrimitiveDateTime) -> ResultValue {
+    ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_typ(2)
+        .fahrt_weg(-1.)
+        .fahrt_zeit(fahrt_zeit)
+        .fahrt_speed(-1.)
+        .fahrt_text(station.into())
+        .fahrt_parameter(1)
+        .build())
+}
+
+fn sample(fahrt_weg: f32, fahrt_zeit: PrimitiveDateTime, fahrt_speed: f32) -> ResultValue {
+    ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_weg(fahrt_weg)
+        .fahrt_zeit(fahrt_zeit)
+        .fahrt_speed(fahrt_speed)
+        .build())
+}
+
+fn result(delay: i64) -> ZusiResult {
+    let offset = Duration::minutes(delay);
+    ZusiResult::builder()
+        .zugnummer("2083".into())
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            // scheduled 09:00
+            arrival("Hofgeismar", 0., datetime!(2020-07-06 8:58), 44018.375),
+            sample(0., datetime!(2020-07-06 8:59), 0.),
+            departure("Hofgeismar", datetime!(2020-07-06 9:00)),
+            sample(1000., datetime!(2020-07-06 9:01), 20.),
+            // scheduled 09:03
+            arrival("Hümme", 2000., datetime!(2020-07-06 9:02) + offset, 44018.377083),
+            departure("Hümme", datetime!(2020-07-06 9:03) + offset),
+            sample(3200., datetime!(2020-07-06 9:04) + offset, 20.),
+            // scheduled 09:06
+            arrival("Grebenstein", 4000., datetime!(2020-07-06 9:05) + offset, 44018.379167),
+        ])
+        .build()
+}
+
+#[test]
+fn test_segments() {
+    let analyser = ResultAnalyser::new(result(0));
+    let segments = analyser.segments();
+
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[0].result().from, "Hofgeismar");
+    assert_eq!(segments[0].result().to, "Hümme");
+    assert_eq!(segments[0].result().scheduled_running_time(), Some(Duration::minutes(3)));
+    assert_eq!(segments[0].distance().unwrap(), 2000.);
+    assert_eq!(segments[0].driving_time().unwrap(), Duration::minutes(3));
+    assert_eq!(segments[1].result().from, "Hümme");
+    assert_eq!(segments[1].result().to, "Grebenstein");
+    assert_eq!(segments[1].distance().unwrap(), 2000.);
+    assert_eq!(segments[1].driving_time().unwrap(), Duration::minutes(3));
+    assert_eq!(segments[1].result().as_ref().zugnummer, "2083");
+}
+
+#[test]
+fn test_segments_skip_rows_without_station() {
+    let mut result = result(0);
+    result.value.push(sample(5000., datetime!(2020-07-06 9:06), 20.));
+    result.value.push(arrival("", 6000., datetime!(2020-07-06 9:07), 44018.38125));
+
+    let analyser = ResultAnalyser::new(result);
+    let segments = analyser.segments();
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[1].result().to, "Grebenstein");
+}
+
+#[test]
+fn test_segments_without_timetable() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            sample(0., datetime!(2020-07-06 9:00), 0.),
+            sample(1000., datetime!(2020-07-06 9:01), 20.),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.segments().len(), 0);
+}
+
+#[test]
+fn test_segments_in_group() {
+    let segments: Vec<_> = [result(0), result(2)].iter()
+        .flat_map(|result| ResultAnalyser::new(result).segments())
+        .filter(|segment| segment.result().from == "Hofgeismar")
+        .collect();
+
+    let analyser_group = ResultAnalyserGroup::new(segments).unwrap();
+    assert_eq!(analyser_group.total_distance().unwrap(), 4000.);
+    assert_eq!(analyser_group.total_driving_time().unwrap(), Duration::minutes(8));
+}
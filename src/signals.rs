@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use time::PrimitiveDateTime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::events::{Event, EventKind};
+use crate::samples::{is_measurement, NO_VALUE};
+
+#[cfg(test)]
+mod tests;
+
+/// The passing of a signal, see [EventKind::SignalPassed].
+#[derive(PartialEq, Debug, Clone)]
+pub struct SignalPassing {
+    /// Name of the signal as written by Zusi, e.g. `Sbk 410 410`.
+    pub signal: String,
+    pub fahrt_zeit: PrimitiveDateTime,
+    pub fahrt_weg: f32,
+    pub fahrt_km: f32,
+    /// Speed at passing in m/s.
+    pub speed: f32,
+    /// Signal limit of the last measurement sample before the signal in m/s.
+    /// [None] if no signal limit was in force.
+    pub approach_limit: Option<f32>,
+    /// Whether the speed exceeded the approach limit by more than the tolerance.
+    pub above_limit: bool,
+}
+
+impl SignalPassing {
+    /// Speed above the approach limit in m/s, zero if the signal was not passed above the limit.
+    pub fn overspeed(&self) -> f32 {
+        self.approach_limit.map_or(0., |limit| (self.speed - limit).max(0.))
+    }
+}
+
+/// Passing speeds of a signal over several runs.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct SignalStatistics {
+    pub passings: usize,
+    /// Mean passing speed in m/s.
+    pub mean_speed: f32,
+    /// Lowest passing speed in m/s.
+    pub min_speed: f32,
+    /// Highest passing speed in m/s.
+    pub max_speed: f32,
+    /// Number of passings above the approach limit.
+    pub above_limit: usize,
+}
+
+impl SignalStatistics {
+    /// Share of the passings above the approach limit between 0 and 1.
+    pub fn above_limit_share(&self) -> f32 {
+        self.above_limit as f32 / self.passings as f32
+    }
+}
+
+/// Lists all passed signals of a run in the order they were passed.
+///
+/// Zusi writes the passing as a measurement sample, so its speed and position are used.
+/// If a passing has no position, those of the last measurement sample are taken instead
+/// and passings before the first measurement sample are skipped.
+///
+/// A signal counts as passed above the limit if its speed exceeds the approach limit by more than `tolerance` in m/s.
+pub fn signal_log(result: &ZusiResult, tolerance: f32) -> Vec<SignalPassing> {
+    let mut last_sample: Option<&FahrtEintrag> = None;
+    let mut log = vec![];
+
+    for value in result.value.iter() {
+        let ResultValue::FahrtEintrag(fahrt_eintrag) = value;
+
+        if let Some(EventKind::SignalPassed { signal }) = Event::from_fahrt_eintrag(fahrt_eintrag).map(|event| event.kind) {
+            let position = if is_measurement(fahrt_eintrag) { Some(fahrt_eintrag) } else { last_sample };
+            if let Some(position) = position {
+                let approach_limit = last_sample
+                    .map(|sample| sample.fahrt_speed_signal)
+                    .filter(|limit| *limit != NO_VALUE);
+
+                log.push(SignalPassing {
+                    signal: signal.to_owned(),
+                    fahrt_zeit: fahrt_eintrag.fahrt_zeit,
+                    fahrt_weg: position.fahrt_weg,
+                    fahrt_km: position.fahrt_km,
+                    speed: position.fahrt_speed,
+                    approach_limit,
+                    above_limit: approach_limit.is_some_and(|limit| position.fahrt_speed > limit + tolerance),
+                });
+            }
+        }
+
+        if is_measurement(fahrt_eintrag) {
+            last_sample = Some(fahrt_eintrag);
+        }
+    }
+
+    log
+}
+
+/// Groups passings by the name of their signal and computes the statistics of each signal.
+/// Passings of signals without a name are skipped, as they can't be told apart.
+pub fn signal_statistics<'a>(passings: impl IntoIterator<Item = &'a SignalPassing>) -> BTreeMap<String, SignalStatistics> {
+    let mut statistics: BTreeMap<String, SignalStatistics> = BTreeMap::new();
+
+    for passing in passings.into_iter().filter(|passing| !passing.signal.is_empty()) {
+        let above_limit = usize::from(passing.above_limit);
+        match statistics.get_mut(&passing.signal) {
+            Some(signal) => {
+                signal.mean_speed += (passing.speed - signal.mean_speed) / (signal.passings + 1) as f32;
+                signal.passings += 1;
+                signal.min_speed = signal.min_speed.min(passing.speed);
+                signal.max_speed = signal.max_speed.max(passing.speed);
+                signal.above_limit += above_limit;
+            }
+            None => {
+                statistics.insert(passing.signal.clone(), SignalStatistics {
+                    passings: 1,
+                    mean_speed: passing.speed,
+                    min_speed: passing.speed,
+                    max_speed: passing.speed,
+                    above_limit,
+                });
+            }
+        }
+    }
+
+    statistics
+}
@@ -0,0 +1,103 @@
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::fixtures::{run, sample};
+use crate::signals::{signal_statistics, SignalPassing};
+use crate::speed_limit::LimitSource;
+
+fn signal(second: i64, fahrt_weg: f32, fahrt_speed: f32, limit: Option<(LimitSource, f32)>, name: &str) -> ResultValue {
+    let ResultValue::FahrtEintrag(mut fahrt_eintrag) = sample(second, fahrt_weg, fahrt_speed, limit);
+    fahrt_eintrag.fahrt_typ = 5;
+    fahrt_eintrag.fahrt_text = name.into();
+    ResultValue::FahrtEintrag(fahrt_eintrag)
+}
+
+fn passing(name: &str, speed: f32, above_limit: bool) -> SignalPassing {
+    SignalPassing {
+        signal: name.to_owned(),
+        fahrt_zeit: datetime!(2019-01-01 23:00),
+        fahrt_weg: 0.,
+        fahrt_km: 0.,
+        speed,
+        approach_limit: None,
+        above_limit,
+    }
+}
+
+#[test]
+fn test_signal_log() {
+    let analyser = run(vec![
+        sample(0, 0., 20., None),
+        signal(5, 100., 20., Some((LimitSource::Signal, 27.7778)), "Sbk 410 410"),
+        sample(10, 200., 20., Some((LimitSource::Signal, 27.7778))),
+        signal(15, 300., 30., None, "Abzw Veddel 43F"),
+        sample(20, 400., 25., None),
+    ]);
+
+    let log = analyser.signal_log(0.);
+    assert_eq!(log, vec![
+        SignalPassing {
+            signal: "Sbk 410 410".to_owned(),
+            fahrt_zeit: datetime!(2019-01-01 23:00:05),
+            fahrt_weg: 100.,
+            fahrt_km: 0.1,
+            speed: 20.,
+            approach_limit: None,
+            above_limit: false,
+        },
+        SignalPassing {
+            signal: "Abzw Veddel 43F".to_owned(),
+            fahrt_zeit: datetime!(2019-01-01 23:00:15),
+            fahrt_weg: 300.,
+            fahrt_km: 0.3,
+            speed: 30.,
+            approach_limit: Some(27.7778),
+            above_limit: true,
+        },
+    ]);
+    assert!((log[1].overspeed() - 2.2222).abs() < 1e-3);
+    assert_eq!(log[0].overspeed(), 0.);
+
+    assert!(!analyser.signal_log(5.)[1].above_limit);
+}
+
+#[test]
+fn test_signal_log_without_position() {
+    let analyser = run(vec![
+        signal(0, -1., -1., None, "Asig A"),
+        sample(5, 100., 10., Some((LimitSource::Signal, 16.6667))),
+        signal(6, -1., -1., None, "Zsig B"),
+    ]);
+
+    let log = analyser.signal_log(0.);
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].signal, "Zsig B");
+    assert_eq!(log[0].fahrt_zeit, datetime!(2019-01-01 23:00:06));
+    assert_eq!(log[0].fahrt_weg, 100.);
+    assert_eq!(log[0].speed, 10.);
+    assert_eq!(log[0].approach_limit, Some(16.6667));
+}
+
+#[test]
+fn test_signal_statistics() {
+    let passings = vec![
+        passing("Sbk 410 410", 20., false),
+        passing("Asig A", 10., false),
+        passing("Sbk 410 410", 30., true),
+        passing("Sbk 410 410", 25., false),
+        passing("", 40., true),
+    ];
+
+    let statistics = signal_statistics(&passings);
+    assert_eq!(statistics.keys().collect::<Vec<_>>(), vec!["Asig A", "Sbk 410 410"]);
+
+    let signal = statistics["Sbk 410 410"];
+    assert_eq!(signal.passings, 3);
+    assert_eq!(signal.mean_speed, 25.);
+    assert_eq!(signal.min_speed, 20.);
+    assert_eq!(signal.max_speed, 30.);
+    assert_eq!(signal.above_limit, 1);
+    assert!((signal.above_limit_share() - 1. / 3.).abs() < 1e-6);
+
+    assert!(signal_statistics(&[]).is_empty());
+}
@@ -0,0 +1,155 @@
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::result_analyser::AnalyseError;
+use crate::samples::{NO_VALUE, Samples};
+
+#[cfg(test)]
+mod tests;
+
+/// The source of a speed limit.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LimitSource {
+    /// Limit of the track, taken from the `fahrt_speed_strecke` attribute.
+    Track,
+    /// Limit of the signals, taken from the `fahrt_speed_signal` attribute.
+    Signal,
+    /// Limit of the train protection system, taken from the `fahrt_speed_zugsicherung` attribute.
+    TrainProtection,
+}
+
+impl LimitSource {
+    /// Name of the source in exports and reports, kept free of spaces so it can be used as a value in CSV and TSV.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LimitSource::Track => "track",
+            LimitSource::Signal => "signal",
+            LimitSource::TrainProtection => "train_protection",
+        }
+    }
+}
+
+/// The lowest of all active speed limits at a measurement sample.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct EffectiveLimit {
+    /// Speed limit in m/s.
+    pub speed: f32,
+    /// The source of the binding limit.
+    pub source: LimitSource,
+}
+
+impl EffectiveLimit {
+    /// Computes the effective limit of a [FahrtEintrag] by taking the lowest active limit.
+    /// If several limits are equal, [LimitSource::Track] wins over [LimitSource::Signal] which wins over [LimitSource::TrainProtection].
+    ///
+    /// Returns [None] if no limit is active.
+    pub fn from_fahrt_eintrag(fahrt_eintrag: &FahrtEintrag) -> Option<EffectiveLimit> {
+        [
+            (fahrt_eintrag.fahrt_speed_strecke, LimitSource::Track),
+            (fahrt_eintrag.fahrt_speed_signal, LimitSource::Signal),
+            (fahrt_eintrag.fahrt_speed_zugsicherung, LimitSource::TrainProtection),
+        ]
+            .into_iter()
+            .filter(|(speed, _)| *speed != NO_VALUE)
+            .fold(None, |lowest: Option<EffectiveLimit>, (speed, source)| match lowest {
+                Some(lowest) if lowest.speed <= speed => Some(lowest),
+                _ => Some(EffectiveLimit { speed, source }),
+            })
+    }
+}
+
+/// A period in which the train continuously exceeded the effective limit.
+#[derive(PartialEq, Debug, Clone)]
+pub struct OverspeedInterval {
+    pub start: PrimitiveDateTime,
+    pub end: PrimitiveDateTime,
+    pub start_km: f32,
+    pub end_km: f32,
+    /// Highest difference between speed and effective limit within the interval in m/s.
+    pub peak_overspeed: f32,
+    /// The source of the binding limit at the moment of the peak overspeed.
+    pub source: LimitSource,
+}
+
+impl OverspeedInterval {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// Result of a speed limit compliance analysis.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SpeedLimitCompliance {
+    pub time_above_limit: Duration,
+    /// Distance travelled above the limit in m.
+    pub distance_above_limit: f32,
+    /// Highest difference between speed and effective limit in m/s, `0.` if the limit was never exceeded.
+    pub peak_overspeed: f32,
+    pub intervals: Vec<OverspeedInterval>,
+}
+
+/// Compares the speed of every measurement sample against its [EffectiveLimit].
+/// A sample counts as overspeed if its speed exceeds the limit by more than `tolerance` (m/s).
+/// The state of a sample is assumed to last until the next sample.
+///
+/// Throws [AnalyseError::NoEntries] if there are no measurement samples.
+pub fn speed_limit_compliance(samples: &Samples, tolerance: f32) -> Result<SpeedLimitCompliance, AnalyseError> {
+    if samples.is_empty() {
+        return Err(AnalyseError::NoEntries);
+    }
+
+    let mut compliance = SpeedLimitCompliance {
+        time_above_limit: Duration::seconds(0),
+        distance_above_limit: 0.,
+        peak_overspeed: 0.,
+        intervals: vec![],
+    };
+    let mut current_interval: Option<OverspeedInterval> = None;
+
+    for (index, sample) in samples.iter().enumerate() {
+        let overspeed = EffectiveLimit::from_fahrt_eintrag(sample)
+            .map(|limit| (sample.fahrt_speed - limit.speed, limit.source))
+            .filter(|(overspeed, _)| *overspeed > tolerance);
+
+        match (overspeed, current_interval.as_mut()) {
+            (Some((overspeed, source)), Some(interval)) => {
+                interval.end = sample.fahrt_zeit;
+                interval.end_km = sample.fahrt_km;
+                if overspeed > interval.peak_overspeed {
+                    interval.peak_overspeed = overspeed;
+                    interval.source = source;
+                }
+            }
+            (Some((overspeed, source)), None) => {
+                current_interval = Some(OverspeedInterval {
+                    start: sample.fahrt_zeit,
+                    end: sample.fahrt_zeit,
+                    start_km: sample.fahrt_km,
+                    end_km: sample.fahrt_km,
+                    peak_overspeed: overspeed,
+                    source,
+                });
+            }
+            (None, Some(interval)) => {
+                interval.end = sample.fahrt_zeit;
+                interval.end_km = sample.fahrt_km;
+                compliance.intervals.push(current_interval.take().unwrap());
+            }
+            (None, None) => {}
+        }
+
+        if let Some((overspeed, _)) = overspeed {
+            compliance.peak_overspeed = compliance.peak_overspeed.max(overspeed);
+            if let Some(next) = samples.get(index + 1) {
+                compliance.time_above_limit += next.fahrt_zeit - sample.fahrt_zeit;
+                compliance.distance_above_limit += next.fahrt_weg - sample.fahrt_weg;
+            }
+        }
+    }
+
+    if let Some(interval) = current_interval {
+        compliance.intervals.push(interval);
+    }
+
+    Ok(compliance)
+}
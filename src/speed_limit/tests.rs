@@ -0,0 +1,105 @@
+use time::Duration;
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::fixtures::{self, run};
+use crate::result_analyser::AnalyseError;
+use crate::speed_limit::{EffectiveLimit, LimitSource};
+
+#[test]
+fn test_effective_limit() {
+    let fahrt_eintrag = FahrtEintrag::builder()
+        .fahrt_zeit(datetime!(2019-01-01 23:18))
+        .fahrt_speed_strecke(33.3333)
+        .fahrt_speed_signal(-1.)
+        .fahrt_speed_zugsicherung(23.6111)
+        .build();
+
+    assert_eq!(
+        EffectiveLimit::from_fahrt_eintrag(&fahrt_eintrag),
+        Some(EffectiveLimit { speed: 23.6111, source: LimitSource::TrainProtection })
+    );
+}
+
+#[test]
+fn test_effective_limit_equal() {
+    let fahrt_eintrag = FahrtEintrag::builder()
+        .fahrt_zeit(datetime!(2019-01-01 23:18))
+        .fahrt_speed_strecke(33.3333)
+        .fahrt_speed_signal(22.2222)
+        .fahrt_speed_zugsicherung(22.2222)
+        .build();
+
+    assert_eq!(
+        EffectiveLimit::from_fahrt_eintrag(&fahrt_eintrag),
+        Some(EffectiveLimit { speed: 22.2222, source: LimitSource::Signal })
+    );
+}
+
+#[test]
+fn test_effective_limit_none() {
+    let fahrt_eintrag = FahrtEintrag::builder()
+        .fahrt_zeit(datetime!(2019-01-01 23:18))
+        .fahrt_speed_strecke(-1.)
+        .fahrt_speed_signal(-1.)
+        .fahrt_speed_zugsicherung(-1.)
+        .build();
+
+    assert_eq!(EffectiveLimit::from_fahrt_eintrag(&fahrt_eintrag), None);
+}
+
+/// A sample with a track limit of 30 m/s and the given signal limit.
+fn sample(fahrt_weg: f32, minute: i64, fahrt_speed: f32, fahrt_speed_signal: f32) -> ResultValue {
+    let ResultValue::FahrtEintrag(mut fahrt_eintrag) = fixtures::sample(minute * 60, fahrt_weg, fahrt_speed, Some((LimitSource::Track, 30.)));
+    fahrt_eintrag.fahrt_speed_signal = fahrt_speed_signal;
+    ResultValue::FahrtEintrag(fahrt_eintrag)
+}
+
+#[test]
+fn test_speed_limit_compliance() {
+    let analyser = run(vec![
+        sample(0., 0, 20., -1.),
+        sample(100., 1, 32., -1.),
+        sample(200., 2, 25., 20.),
+        sample(300., 3, 18., 20.),
+        sample(400., 4, 31., -1.),
+        sample(500., 5, 30.5, -1.),
+    ]);
+    let compliance = analyser.speed_limit_compliance(0.).unwrap();
+    assert_eq!(compliance.time_above_limit, Duration::minutes(3));
+    assert_eq!(compliance.distance_above_limit, 300.);
+    assert_eq!(compliance.peak_overspeed, 5.);
+    assert_eq!(compliance.intervals.len(), 2);
+    assert_eq!(compliance.intervals[0].start, datetime!(2019-01-01 23:01));
+    assert_eq!(compliance.intervals[0].end, datetime!(2019-01-01 23:03));
+    assert_eq!(compliance.intervals[0].start_km, 0.1);
+    assert_eq!(compliance.intervals[0].end_km, 0.3);
+    assert_eq!(compliance.intervals[0].peak_overspeed, 5.);
+    assert_eq!(compliance.intervals[0].source, LimitSource::Signal);
+    assert_eq!(compliance.intervals[1].start, datetime!(2019-01-01 23:04));
+    assert_eq!(compliance.intervals[1].end, datetime!(2019-01-01 23:05));
+    assert_eq!(compliance.intervals[1].source, LimitSource::Track);
+}
+
+#[test]
+fn test_speed_limit_compliance_tolerance() {
+    let analyser = run(vec![
+        sample(0., 0, 20., -1.),
+        sample(100., 1, 32., -1.),
+        sample(200., 2, 25., 20.),
+        sample(300., 3, 18., 20.),
+    ]);
+    let compliance = analyser.speed_limit_compliance(2.5).unwrap();
+    assert_eq!(compliance.time_above_limit, Duration::minutes(1));
+    assert_eq!(compliance.distance_above_limit, 100.);
+    assert_eq!(compliance.intervals.len(), 1);
+    assert_eq!(compliance.intervals[0].start, datetime!(2019-01-01 23:02));
+    assert_eq!(compliance.intervals[0].duration(), Duration::minutes(1));
+}
+
+#[test]
+fn test_speed_limit_compliance_0() {
+    let analyser = run(vec![]);
+    assert_eq!(analyser.speed_limit_compliance(0.), Err(AnalyseError::NoEntries));
+}
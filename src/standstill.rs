@@ -0,0 +1,163 @@
+use std::ops::AddAssign;
+
+use time::{Duration, PrimitiveDateTime};
+
+use crate::events::{Event, EventKind};
+use crate::result_analyser::AnalyseError;
+use crate::samples::Samples;
+
+#[cfg(test)]
+mod tests;
+
+/// Classification of a [Standstill].
+#[derive(PartialEq, Debug, Clone)]
+pub enum StandstillKind {
+    /// The standstill lines up with an arrival or departure at a timetable stop.
+    TimetableStop {
+        station: String,
+    },
+    /// The train stood outside of a timetable stop.
+    Unscheduled,
+}
+
+/// A period in which the train did not move.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Standstill {
+    pub start: PrimitiveDateTime,
+    pub end: PrimitiveDateTime,
+    pub fahrt_weg: f32,
+    pub fahrt_km: f32,
+    pub kind: StandstillKind,
+}
+
+impl Standstill {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// Totals of scheduled dwell time and unscheduled stop time for one or more runs.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct StandstillSummary {
+    pub scheduled_stops: usize,
+    pub scheduled_dwell_time: Duration,
+    pub unscheduled_stops: usize,
+    pub unscheduled_stop_time: Duration,
+}
+
+impl StandstillSummary {
+    pub fn new() -> StandstillSummary {
+        Self {
+            scheduled_stops: 0,
+            scheduled_dwell_time: Duration::seconds(0),
+            unscheduled_stops: 0,
+            unscheduled_stop_time: Duration::seconds(0),
+        }
+    }
+
+    /// Computes the average dwell time per timetable stop.
+    ///
+    /// Returns [None] if there are no timetable stops.
+    pub fn average_scheduled_dwell_time(&self) -> Option<Duration> {
+        if self.scheduled_stops == 0 {
+            None
+        } else {
+            Some(self.scheduled_dwell_time / self.scheduled_stops as u32)
+        }
+    }
+
+    /// Computes the average duration per unscheduled stop.
+    ///
+    /// Returns [None] if there are no unscheduled stops.
+    pub fn average_unscheduled_stop_time(&self) -> Option<Duration> {
+        if self.unscheduled_stops == 0 {
+            None
+        } else {
+            Some(self.unscheduled_stop_time / self.unscheduled_stops as u32)
+        }
+    }
+}
+
+impl Default for StandstillSummary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AddAssign for StandstillSummary {
+    fn add_assign(&mut self, rhs: Self) {
+        self.scheduled_stops += rhs.scheduled_stops;
+        self.scheduled_dwell_time += rhs.scheduled_dwell_time;
+        self.unscheduled_stops += rhs.unscheduled_stops;
+        self.unscheduled_stop_time += rhs.unscheduled_stop_time;
+    }
+}
+
+impl<'a> FromIterator<&'a Standstill> for StandstillSummary {
+    fn from_iter<T: IntoIterator<Item = &'a Standstill>>(iter: T) -> Self {
+        let mut summary = StandstillSummary::new();
+        for standstill in iter {
+            match standstill.kind {
+                StandstillKind::TimetableStop { .. } => {
+                    summary.scheduled_stops += 1;
+                    summary.scheduled_dwell_time += standstill.duration();
+                }
+                StandstillKind::Unscheduled => {
+                    summary.unscheduled_stops += 1;
+                    summary.unscheduled_stop_time += standstill.duration();
+                }
+            }
+        }
+        summary
+    }
+}
+
+/// Detects all periods with zero speed, using the same definition of idle time as [pure_driving_time](crate::result_analyser::ResultAnalyser::pure_driving_time).
+/// A standstill is classified as [StandstillKind::TimetableStop] if a timetable arrival or departure happened within it.
+/// Timetable rows without a station are skipped, see [EventKind::is_timetable_stop].
+///
+/// Throws [AnalyseError::NoEntries] if there are no measurement samples.
+pub fn standstills<'a>(samples: &Samples, events: impl Iterator<Item = Event<'a>>) -> Result<Vec<Standstill>, AnalyseError> {
+    if samples.is_empty() {
+        return Err(AnalyseError::NoEntries);
+    }
+
+    let timetable_events: Vec<(PrimitiveDateTime, &str)> = events
+        .filter(|event| event.kind.is_timetable_stop())
+        .filter_map(|event| match event.kind {
+            EventKind::TimetableArrival { station } | EventKind::TimetableDeparture { station } => Some((event.fahrt_eintrag.fahrt_zeit, station)),
+            _ => None,
+        })
+        .collect();
+
+    let mut standstills: Vec<Standstill> = vec![];
+    let mut current: Option<Standstill> = None;
+
+    for (sample, next) in samples.pairs() {
+        if sample.fahrt_speed > 0. || next.fahrt_speed > 0. {
+            standstills.extend(current.take());
+        } else if let Some(standstill) = current.as_mut() {
+            standstill.end = next.fahrt_zeit;
+        } else {
+            current = Some(Standstill {
+                start: sample.fahrt_zeit,
+                end: next.fahrt_zeit,
+                fahrt_weg: sample.fahrt_weg,
+                fahrt_km: sample.fahrt_km,
+                kind: StandstillKind::Unscheduled,
+            });
+        }
+    }
+    standstills.extend(current);
+
+    for standstill in standstills.iter_mut() {
+        if let Some((_, station)) = timetable_events.iter()
+            .find(|(fahrt_zeit, _)| standstill.start <= *fahrt_zeit && *fahrt_zeit <= standstill.end) {
+            standstill.kind = StandstillKind::TimetableStop {
+                station: station.to_string(),
+            };
+        }
+    }
+
+    Ok(standstills)
+}
@@ -0,0 +1,84 @@
+use time::Duration;
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::fixtures::{self, run};
+use crate::result_analyser::AnalyseError;
+use crate::standstill::{StandstillKind, StandstillSummary};
+
+fn sample(fahrt_weg: f32, minute: i64, fahrt_speed: f32) -> ResultValue {
+    fixtures::sample(minute * 60, fahrt_weg, fahrt_speed, None)
+}
+
+fn timetable_row(fahrt_weg: f32, minute: i64, station: &str, fahrt_parameter: u32) -> ResultValue {
+    let ResultValue::FahrtEintrag(mut fahrt_eintrag) = sample(fahrt_weg, minute, 0.);
+    fahrt_eintrag.fahrt_typ = 2;
+    fahrt_eintrag.fahrt_text = station.into();
+    fahrt_eintrag.fahrt_parameter = fahrt_parameter;
+    ResultValue::FahrtEintrag(fahrt_eintrag)
+}
+
+#[test]
+fn test_standstills() {
+    let analyser = run(vec![
+        timetable_row(0., 0, "Hofgeismar", 0),
+        sample(0., 2, 0.),
+        timetable_row(-1., 3, "Hofgeismar", 1),
+        sample(0., 3, 5.),
+        sample(500., 5, 0.),
+        sample(500., 9, 0.),
+        sample(500., 10, 0.),
+        sample(800., 12, 10.),
+        sample(1500., 13, 0.),
+    ]);
+    let standstills = analyser.standstills().unwrap();
+
+    assert_eq!(standstills.len(), 2);
+    assert_eq!(standstills[0].start, datetime!(2019-01-01 23:00));
+    assert_eq!(standstills[0].end, datetime!(2019-01-01 23:02));
+    assert_eq!(standstills[0].kind, StandstillKind::TimetableStop { station: "Hofgeismar".into() });
+    assert_eq!(standstills[1].start, datetime!(2019-01-01 23:05));
+    assert_eq!(standstills[1].end, datetime!(2019-01-01 23:10));
+    assert_eq!(standstills[1].duration(), Duration::minutes(5));
+    assert_eq!(standstills[1].fahrt_weg, 500.);
+    assert_eq!(standstills[1].fahrt_km, 0.5);
+    assert_eq!(standstills[1].kind, StandstillKind::Unscheduled);
+
+    let summary: StandstillSummary = standstills.iter().collect();
+    assert_eq!(summary.scheduled_stops, 1);
+    assert_eq!(summary.average_scheduled_dwell_time(), Some(Duration::minutes(2)));
+    assert_eq!(summary.unscheduled_stops, 1);
+    assert_eq!(summary.unscheduled_stop_time, Duration::minutes(5));
+}
+
+#[test]
+fn test_standstills_moving() {
+    let analyser = run(vec![
+        sample(0., 0, 5.),
+        sample(500., 5, 0.),
+        sample(800., 12, 10.),
+    ]);
+    assert_eq!(analyser.standstills().unwrap(), vec![]);
+}
+
+#[test]
+fn test_standstills_untexted_timetable_row() {
+    let analyser = run(vec![
+        sample(0., 0, 5.),
+        sample(500., 5, 0.),
+        timetable_row(-1., 6, "", 0),
+        sample(500., 9, 0.),
+        sample(800., 12, 10.),
+    ]);
+    let standstills = analyser.standstills().unwrap();
+
+    assert_eq!(standstills.len(), 1);
+    assert_eq!(standstills[0].kind, StandstillKind::Unscheduled);
+}
+
+#[test]
+fn test_standstills_0() {
+    let analyser = run(vec![]);
+    assert_eq!(analyser.standstills(), Err(AnalyseError::NoEntries));
+    assert_eq!(StandstillSummary::new().average_unscheduled_stop_time(), None);
+}
@@ -0,0 +1,117 @@
+use crate::result_analyser::{AnalyseError, RunSummary};
+
+#[cfg(test)]
+mod tests;
+
+/// A per-run metric which can be aggregated across a [ResultAnalyserGroup](crate::result_analyser_group::ResultAnalyserGroup).
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum RunMetric {
+    /// Distance in m, see [distance](crate::result_analyser::ResultAnalyser::distance).
+    Distance,
+    /// Driving time in s, see [driving_time](crate::result_analyser::ResultAnalyser::driving_time).
+    DrivingTime,
+    /// Pure driving time in s, see [pure_driving_time](crate::result_analyser::ResultAnalyser::pure_driving_time).
+    PureDrivingTime,
+    /// Average speed in m/s, see [average_speed](crate::result_analyser::ResultAnalyser::average_speed).
+    AverageSpeed,
+    /// Pure average speed in m/s, see [pure_average_speed](crate::result_analyser::ResultAnalyser::pure_average_speed).
+    PureAverageSpeed,
+}
+
+impl RunMetric {
+    pub const ALL: [RunMetric; 5] = [
+        RunMetric::Distance,
+        RunMetric::DrivingTime,
+        RunMetric::PureDrivingTime,
+        RunMetric::AverageSpeed,
+        RunMetric::PureAverageSpeed,
+    ];
+
+    /// Reads the metric from a [RunSummary], durations are converted to seconds.
+    pub fn value(&self, summary: &RunSummary) -> Result<f32, AnalyseError> {
+        match self {
+            RunMetric::Distance => summary.distance(),
+            RunMetric::DrivingTime => Ok(summary.driving_time()?.as_seconds_f32()),
+            RunMetric::PureDrivingTime => Ok(summary.pure_driving_time()?.as_seconds_f32()),
+            RunMetric::AverageSpeed => summary.average_speed(),
+            RunMetric::PureAverageSpeed => summary.pure_average_speed(),
+        }
+    }
+}
+
+/// A value of a [Distribution] together with the index of the analyser which produced it.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Extreme {
+    pub index: usize,
+    pub value: f32,
+}
+
+/// Distribution of a metric across several runs.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Distribution {
+    /// All values ascending together with their index.
+    sorted: Vec<Extreme>,
+    mean: f32,
+    standard_deviation: f32,
+}
+
+impl Distribution {
+    /// Creates a distribution from the values of several runs, the position of a value is used as its index.
+    ///
+    /// Returns [None] if there are no values.
+    pub fn new(values: impl IntoIterator<Item = f32>) -> Option<Distribution> {
+        let mut sorted: Vec<Extreme> = values.into_iter()
+            .enumerate()
+            .map(|(index, value)| Extreme { index, value })
+            .collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(|a, b| a.value.total_cmp(&b.value));
+
+        let count = sorted.len() as f32;
+        let mean = sorted.iter().map(|extreme| extreme.value).sum::<f32>() / count;
+        let variance = sorted.iter().map(|extreme| (extreme.value - mean).powi(2)).sum::<f32>() / count;
+
+        Some(Self {
+            sorted,
+            mean,
+            standard_deviation: variance.sqrt(),
+        })
+    }
+
+    /// Number of values, never zero.
+    pub fn count(&self) -> usize {
+        self.sorted.len()
+    }
+
+    pub fn min(&self) -> Extreme {
+        self.sorted[0]
+    }
+
+    pub fn max(&self) -> Extreme {
+        self.sorted[self.sorted.len() - 1]
+    }
+
+    pub fn mean(&self) -> f32 {
+        self.mean
+    }
+
+    pub fn median(&self) -> f32 {
+        self.percentile(50.)
+    }
+
+    /// Computes the given percentile (0 to 100) by linear interpolation between the closest ranks.
+    pub fn percentile(&self, percentile: f32) -> f32 {
+        let rank = percentile.clamp(0., 100.) / 100. * (self.sorted.len() - 1) as f32;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let lower_value = self.sorted[lower].value;
+        lower_value + (self.sorted[upper].value - lower_value) * (rank - lower as f32)
+    }
+
+    /// Computes the population standard deviation.
+    pub fn standard_deviation(&self) -> f32 {
+        self.standard_deviation
+    }
+}
@@ -0,0 +1,31 @@
+use crate::statistics::{Distribution, Extreme};
+
+#[test]
+fn test_distribution() {
+    let distribution = Distribution::new(vec![4., 1., 3., 2.]).unwrap();
+
+    assert_eq!(distribution.count(), 4);
+    assert_eq!(distribution.min(), Extreme { index: 1, value: 1. });
+    assert_eq!(distribution.max(), Extreme { index: 0, value: 4. });
+    assert_eq!(distribution.mean(), 2.5);
+    assert_eq!(distribution.median(), 2.5);
+    assert_eq!(distribution.percentile(0.), 1.);
+    assert_eq!(distribution.percentile(100.), 4.);
+    assert_eq!(distribution.percentile(25.), 1.75);
+    assert_eq!(distribution.standard_deviation(), 1.25_f32.sqrt());
+}
+
+#[test]
+fn test_distribution_single_value() {
+    let distribution = Distribution::new(vec![7.]).unwrap();
+
+    assert_eq!(distribution.min(), distribution.max());
+    assert_eq!(distribution.median(), 7.);
+    assert_eq!(distribution.percentile(90.), 7.);
+    assert_eq!(distribution.standard_deviation(), 0.);
+}
+
+#[test]
+fn test_distribution_0() {
+    assert_eq!(Distribution::new(vec![]), None);
+}
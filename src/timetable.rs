@@ -0,0 +1,159 @@
+use time::{Duration, PrimitiveDateTime};
+use time::macros::datetime;
+
+use crate::events::{Event, EventKind};
+use crate::result_analyser::AnalyseError;
+
+#[cfg(test)]
+mod tests;
+
+/// Day zero of the Delphi `TDateTime` format used for the `fahrt_fpl_ank` and `fahrt_fpl_abf` attributes.
+const DELPHI_EPOCH: PrimitiveDateTime = datetime!(1899-12-30 0:00);
+
+/// Converts a Delphi day number, e.g. `35825.390625`, into a [PrimitiveDateTime].
+/// The integral part counts the days since 1899-12-30, the fractional part is the time of day.
+/// The result is rounded to whole seconds.
+pub fn datetime_from_delphi(days: f64) -> PrimitiveDateTime {
+    DELPHI_EPOCH + Duration::seconds((days * 86400.).round() as i64)
+}
+
+/// Scheduled and actual times of a single timetable stop.
+#[derive(PartialEq, Debug, Clone)]
+pub struct StopPunctuality {
+    pub station: String,
+    pub scheduled_arrival: Option<PrimitiveDateTime>,
+    pub actual_arrival: Option<PrimitiveDateTime>,
+    pub scheduled_departure: Option<PrimitiveDateTime>,
+    pub actual_departure: Option<PrimitiveDateTime>,
+}
+
+impl StopPunctuality {
+    /// Difference between actual and scheduled arrival, negative if the train arrived early.
+    pub fn arrival_delay(&self) -> Option<Duration> {
+        Some(self.actual_arrival? - self.scheduled_arrival?)
+    }
+
+    /// Difference between actual and scheduled departure, negative if the train departed early.
+    pub fn departure_delay(&self) -> Option<Duration> {
+        Some(self.actual_departure? - self.scheduled_departure?)
+    }
+
+    /// Difference between actual and scheduled dwell time, positive if the train stood longer than scheduled.
+    pub fn dwell_deviation(&self) -> Option<Duration> {
+        let actual_dwell = self.actual_departure? - self.actual_arrival?;
+        let scheduled_dwell = self.scheduled_departure? - self.scheduled_arrival?;
+        Some(actual_dwell - scheduled_dwell)
+    }
+
+    /// The delay used to decide whether the stop was punctual.
+    /// This is the arrival delay, or the departure delay if there is no scheduled arrival.
+    pub fn delay(&self) -> Option<Duration> {
+        self.arrival_delay().or_else(|| self.departure_delay())
+    }
+}
+
+/// Result of a punctuality analysis for one or more runs.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Punctuality {
+    pub stops: Vec<StopPunctuality>,
+    /// A stop counts as punctual if its [delay](StopPunctuality::delay) does not exceed this threshold.
+    pub threshold: Duration,
+}
+
+impl Punctuality {
+    /// Counts the stops whose delay does not exceed the threshold.
+    pub fn punctual_stops(&self) -> usize {
+        self.delays().filter(|delay| *delay <= self.threshold).count()
+    }
+
+    /// Computes the share of punctual stops among all stops with a known delay.
+    ///
+    /// Returns [None] if no stop has a known delay.
+    pub fn punctual_share(&self) -> Option<f32> {
+        let stops = self.delays().count();
+        if stops == 0 {
+            None
+        } else {
+            Some(self.punctual_stops() as f32 / stops as f32)
+        }
+    }
+
+    /// Computes the average arrival delay of all stops with a known arrival delay.
+    pub fn average_arrival_delay(&self) -> Option<Duration> {
+        average(self.stops.iter().filter_map(StopPunctuality::arrival_delay))
+    }
+
+    /// Computes the average departure delay of all stops with a known departure delay.
+    pub fn average_departure_delay(&self) -> Option<Duration> {
+        average(self.stops.iter().filter_map(StopPunctuality::departure_delay))
+    }
+
+    /// Returns the highest delay of all stops.
+    pub fn max_delay(&self) -> Option<Duration> {
+        self.delays().max()
+    }
+
+    fn delays(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.stops.iter().filter_map(StopPunctuality::delay)
+    }
+}
+
+fn average(durations: impl Iterator<Item = Duration>) -> Option<Duration> {
+    let (sum, count) = durations.fold((Duration::seconds(0), 0), |(sum, count), duration| (sum + duration, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count)
+    }
+}
+
+/// Matches timetable arrivals and departures by their station.
+/// Repeated arrivals at the same station before departing are merged into the first one.
+/// Timetable rows without a station are skipped, see [EventKind::is_timetable_stop].
+///
+/// Throws [AnalyseError::NoTimetableEntries] if there are no timetable stops.
+pub fn punctuality<'a>(events: impl Iterator<Item = Event<'a>>, threshold: Duration) -> Result<Punctuality, AnalyseError> {
+    let mut stops: Vec<StopPunctuality> = vec![];
+
+    for event in events.filter(|event| event.kind.is_timetable_stop()) {
+        match event.kind {
+            EventKind::TimetableArrival { station } => {
+                let already_arrived = stops.last()
+                    .is_some_and(|stop| stop.station == station && stop.actual_departure.is_none());
+                if !already_arrived {
+                    stops.push(StopPunctuality {
+                        station: station.into(),
+                        scheduled_arrival: event.fahrt_eintrag.fahrt_fpl_ank.map(datetime_from_delphi),
+                        actual_arrival: Some(event.fahrt_eintrag.fahrt_zeit),
+                        scheduled_departure: event.fahrt_eintrag.fahrt_fpl_abf.map(datetime_from_delphi),
+                        actual_departure: None,
+                    });
+                }
+            }
+            EventKind::TimetableDeparture { station } => {
+                match stops.last_mut() {
+                    Some(stop) if stop.station == station && stop.actual_departure.is_none() => {
+                        stop.actual_departure = Some(event.fahrt_eintrag.fahrt_zeit);
+                    }
+                    _ => stops.push(StopPunctuality {
+                        station: station.into(),
+                        scheduled_arrival: None,
+                        actual_arrival: None,
+                        scheduled_departure: None,
+                        actual_departure: Some(event.fahrt_eintrag.fahrt_zeit),
+                    }),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if stops.is_empty() {
+        Err(AnalyseError::NoTimetableEntries)
+    } else {
+        Ok(Punctuality {
+            stops,
+            threshold,
+        })
+    }
+}
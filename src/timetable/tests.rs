@@ -0,0 +1,100 @@
+use time::Duration;
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::result_analyser::{AnalyseError, ResultAnalyser};
+use crate::timetable::datetime_from_delphi;
+
+#[test]
+fn test_datetime_from_delphi() {
+    assert_eq!(datetime_from_delphi(35825.390625), datetime!(1998-01-30 9:22:30));
+    assert_eq!(datetime_from_delphi(43410.), datetime!(2018-11-06 0:00));
+}
+
+fn arrival(station: &str, fahrt_zeit: time::PrimitiveDateTime, fahrt_fpl_ank: f64, fahrt_fpl_abf: f64) -> ResultValue {
+    ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_typ(2)
+        .fahrt_weg(100.)
+        .fahrt_zeit(fahrt_zeit)
+        .fahrt_text(station.into())
+        .fahrt_fpl_ank(Some(fahrt_fpl_ank))
+        .fahrt_fpl_abf(Some(fahrt_fpl_abf))
+        .build())
+}
+
+fn departure(station: &str, fahrt_zeit: time::PrimitiveDateTime) -> ResultValue {
+    ResultValue::FahrtEintrag(FahrtEintrag::builder()
+        .fahrt_typ(2)
+        .fahrt_weg(-1.)
+        .fahrt_zeit(fahrt_zeit)
+        .fahrt_speed(-1.)
+        .fahrt_text(station.into())
+        .fahrt_parameter(1)
+        .build())
+}
+
+fn result() -> ZusiResult {
+    ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            // scheduled 09:22:30 - 09:45:00
+            arrival("Kassel Hbf", datetime!(1998-01-30 9:21:00), 35825.390625, 35825.40625),
+            departure("Kassel Hbf", datetime!(1998-01-30 9:47:00)),
+            // scheduled 09:52:30 - 09:52:30
+            arrival("Obervellmar", datetime!(1998-01-30 9:53:30), 35825.411458, 35825.411458),
+            arrival("Obervellmar", datetime!(1998-01-30 9:54:00), 35825.411458, 35825.411458),
+            departure("Obervellmar", datetime!(1998-01-30 9:55:30)),
+            // timetable row without a station
+            arrival("", datetime!(1998-01-30 9:58:00), 35825.415, 35825.415),
+        ])
+        .build()
+}
+
+#[test]
+fn test_punctuality() {
+    let analyser = ResultAnalyser::new(result());
+    let punctuality = analyser.punctuality(Duration::minutes(2)).unwrap();
+
+    assert_eq!(punctuality.stops.len(), 2);
+    assert_eq!(punctuality.stops[0].station, "Kassel Hbf");
+    assert_eq!(punctuality.stops[0].arrival_delay(), Some(Duration::seconds(-90)));
+    assert_eq!(punctuality.stops[0].departure_delay(), Some(Duration::minutes(2)));
+    assert_eq!(punctuality.stops[0].dwell_deviation(), Some(Duration::seconds(210)));
+    assert_eq!(punctuality.stops[1].station, "Obervellmar");
+    assert_eq!(punctuality.stops[1].actual_arrival, Some(datetime!(1998-01-30 9:53:30)));
+    assert_eq!(punctuality.stops[1].arrival_delay(), Some(Duration::minutes(1)));
+    assert_eq!(punctuality.stops[1].departure_delay(), Some(Duration::minutes(3)));
+    assert_eq!(punctuality.stops[1].dwell_deviation(), Some(Duration::minutes(2)));
+
+    assert_eq!(punctuality.punctual_stops(), 2);
+    assert_eq!(punctuality.punctual_share(), Some(1.));
+    assert_eq!(punctuality.average_arrival_delay(), Some(Duration::seconds(-15)));
+    assert_eq!(punctuality.average_departure_delay(), Some(Duration::seconds(150)));
+    assert_eq!(punctuality.max_delay(), Some(Duration::minutes(1)));
+}
+
+#[test]
+fn test_punctuality_threshold() {
+    let analyser = ResultAnalyser::new(result());
+    let punctuality = analyser.punctuality(Duration::seconds(30)).unwrap();
+
+    assert_eq!(punctuality.punctual_stops(), 1);
+    assert_eq!(punctuality.punctual_share(), Some(0.5));
+}
+
+#[test]
+fn test_punctuality_0() {
+    let result = ZusiResult::builder()
+        .datum(datetime!(2019-01-01 23:14))
+        .value(vec![
+            ResultValue::FahrtEintrag(FahrtEintrag::builder()
+                .fahrt_weg(2.33)
+                .fahrt_zeit(datetime!(2019-01-01 23:18))
+                .build()),
+        ])
+        .build();
+
+    let analyser = ResultAnalyser::new(result);
+    assert_eq!(analyser.punctuality(Duration::minutes(2)), Err(AnalyseError::NoTimetableEntries));
+}
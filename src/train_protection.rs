@@ -0,0 +1,215 @@
+use std::ops::AddAssign;
+
+use time::{Duration, PrimitiveDateTime};
+use zusi_xml_lib::xml::zusi::result::{ResultValue, ZusiResult};
+use zusi_xml_lib::xml::zusi::result::fahrt_eintrag::FahrtEintrag;
+
+use crate::events::{Event, EventKind, TrainProtectionFlags};
+use crate::result_analyser::AnalyseError;
+use crate::samples::{is_measurement, NO_VALUE, Samples};
+
+#[cfg(test)]
+mod tests;
+
+/// A period in which the train was supervised against a restrictive braking curve,
+/// see [TrainProtectionFlags::RESTRICTIVE_SUPERVISION].
+#[derive(PartialEq, Debug, Clone)]
+pub struct RestrictiveSupervision {
+    pub start: PrimitiveDateTime,
+    pub end: PrimitiveDateTime,
+    pub start_fahrt_weg: f32,
+    pub end_fahrt_weg: f32,
+    pub start_km: f32,
+    /// Lowest train protection limit of the measurement samples within the period in m/s.
+    /// [None] if no limit was active.
+    pub lowest_limit: Option<f32>,
+}
+
+impl RestrictiveSupervision {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// Distance driven under supervision in m.
+    pub fn distance(&self) -> f32 {
+        self.end_fahrt_weg - self.start_fahrt_weg
+    }
+}
+
+/// A brake application by the train protection system, see [TrainProtectionFlags::FORCED_BRAKE].
+/// Position and speed are taken from the last measurement sample before the event.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ForcedBrake {
+    pub fahrt_zeit: PrimitiveDateTime,
+    pub fahrt_weg: f32,
+    pub fahrt_km: f32,
+    /// Speed in m/s.
+    pub speed: f32,
+}
+
+/// Result of a train protection analysis.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct TrainProtection {
+    /// Number of influences which had to be acknowledged.
+    pub influences: usize,
+    pub acknowledgements: usize,
+    pub restrictive_supervisions: Vec<RestrictiveSupervision>,
+    pub forced_brakes: Vec<ForcedBrake>,
+}
+
+impl TrainProtection {
+    pub fn restrictive_supervision_time(&self) -> Duration {
+        self.restrictive_supervisions.iter().map(RestrictiveSupervision::duration).sum()
+    }
+
+    /// Distance driven under restrictive supervision in m.
+    pub fn restrictive_supervision_distance(&self) -> f32 {
+        self.restrictive_supervisions.iter().map(RestrictiveSupervision::distance).sum()
+    }
+}
+
+/// Totals of the train protection analysis of several runs.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct TrainProtectionSummary {
+    pub influences: usize,
+    pub acknowledgements: usize,
+    pub restrictive_supervisions: usize,
+    pub restrictive_supervision_time: Duration,
+    /// Distance driven under restrictive supervision in m.
+    pub restrictive_supervision_distance: f32,
+    pub forced_brakes: usize,
+}
+
+impl TrainProtectionSummary {
+    pub fn new() -> TrainProtectionSummary {
+        Self {
+            influences: 0,
+            acknowledgements: 0,
+            restrictive_supervisions: 0,
+            restrictive_supervision_time: Duration::seconds(0),
+            restrictive_supervision_distance: 0.,
+            forced_brakes: 0,
+        }
+    }
+}
+
+impl Default for TrainProtectionSummary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AddAssign for TrainProtectionSummary {
+    fn add_assign(&mut self, rhs: Self) {
+        self.influences += rhs.influences;
+        self.acknowledgements += rhs.acknowledgements;
+        self.restrictive_supervisions += rhs.restrictive_supervisions;
+        self.restrictive_supervision_time += rhs.restrictive_supervision_time;
+        self.restrictive_supervision_distance += rhs.restrictive_supervision_distance;
+        self.forced_brakes += rhs.forced_brakes;
+    }
+}
+
+impl From<&TrainProtection> for TrainProtectionSummary {
+    fn from(train_protection: &TrainProtection) -> Self {
+        Self {
+            influences: train_protection.influences,
+            acknowledgements: train_protection.acknowledgements,
+            restrictive_supervisions: train_protection.restrictive_supervisions.len(),
+            restrictive_supervision_time: train_protection.restrictive_supervision_time(),
+            restrictive_supervision_distance: train_protection.restrictive_supervision_distance(),
+            forced_brakes: train_protection.forced_brakes.len(),
+        }
+    }
+}
+
+/// Decodes the [EventKind::TrainProtection] events of a run.
+///
+/// Each event reports the current state of the train protection system and Zusi usually writes every state twice,
+/// so only changes of the state are counted:
+/// - an influence when [INFLUENCE](TrainProtectionFlags::INFLUENCE) is set,
+/// - an acknowledgement when [ACKNOWLEDGEMENT](TrainProtectionFlags::ACKNOWLEDGEMENT) is reported without a pending influence,
+/// - a forced brake when [FORCED_BRAKE](TrainProtectionFlags::FORCED_BRAKE) is set.
+///
+/// A restrictive supervision lasts from setting [RESTRICTIVE_SUPERVISION](TrainProtectionFlags::RESTRICTIVE_SUPERVISION)
+/// until the first event without it or the end of the run.
+///
+/// Throws [AnalyseError::NoEntries] if there are no measurement samples.
+pub fn train_protection(result: &ZusiResult) -> Result<TrainProtection, AnalyseError> {
+    let mut last_sample = Samples::new(result).first().ok_or(AnalyseError::NoEntries)?;
+    let mut train_protection = TrainProtection::default();
+    let mut state = TrainProtectionFlags::empty();
+    let mut current: Option<RestrictiveSupervision> = None;
+
+    for value in result.value.iter() {
+        let ResultValue::FahrtEintrag(fahrt_eintrag) = value;
+
+        if is_measurement(fahrt_eintrag) {
+            last_sample = fahrt_eintrag;
+            if let Some(supervision) = current.as_mut() {
+                supervision.end = fahrt_eintrag.fahrt_zeit;
+                supervision.end_fahrt_weg = fahrt_eintrag.fahrt_weg;
+                supervision.lowest_limit = lowest_limit(supervision.lowest_limit, fahrt_eintrag);
+            }
+        }
+
+        let Some(EventKind::TrainProtection(flags)) = Event::from_fahrt_eintrag(fahrt_eintrag).map(|event| event.kind) else {
+            continue;
+        };
+        let was_set = |flag| state.contains(flag);
+        let is_set = |flag| flags.contains(flag);
+
+        if is_set(TrainProtectionFlags::INFLUENCE) && !was_set(TrainProtectionFlags::INFLUENCE) {
+            train_protection.influences += 1;
+        }
+        if is_acknowledged(flags) && !is_acknowledged(state) {
+            train_protection.acknowledgements += 1;
+        }
+        if is_set(TrainProtectionFlags::FORCED_BRAKE) && !was_set(TrainProtectionFlags::FORCED_BRAKE) {
+            train_protection.forced_brakes.push(ForcedBrake {
+                fahrt_zeit: fahrt_eintrag.fahrt_zeit,
+                fahrt_weg: last_sample.fahrt_weg,
+                fahrt_km: last_sample.fahrt_km,
+                speed: last_sample.fahrt_speed,
+            });
+        }
+
+        match (was_set(TrainProtectionFlags::RESTRICTIVE_SUPERVISION), is_set(TrainProtectionFlags::RESTRICTIVE_SUPERVISION)) {
+            (false, true) => {
+                current = Some(RestrictiveSupervision {
+                    start: fahrt_eintrag.fahrt_zeit,
+                    end: fahrt_eintrag.fahrt_zeit,
+                    start_fahrt_weg: last_sample.fahrt_weg,
+                    end_fahrt_weg: last_sample.fahrt_weg,
+                    start_km: last_sample.fahrt_km,
+                    lowest_limit: None,
+                });
+            }
+            (true, false) => {
+                if let Some(mut supervision) = current.take() {
+                    supervision.end = fahrt_eintrag.fahrt_zeit;
+                    train_protection.restrictive_supervisions.push(supervision);
+                }
+            }
+            _ => {}
+        }
+
+        state = flags;
+    }
+    train_protection.restrictive_supervisions.extend(current);
+
+    Ok(train_protection)
+}
+
+fn is_acknowledged(flags: TrainProtectionFlags) -> bool {
+    flags.contains(TrainProtectionFlags::ACKNOWLEDGEMENT) && !flags.contains(TrainProtectionFlags::INFLUENCE)
+}
+
+fn lowest_limit(lowest: Option<f32>, fahrt_eintrag: &FahrtEintrag) -> Option<f32> {
+    let limit = fahrt_eintrag.fahrt_speed_zugsicherung;
+    if limit == NO_VALUE {
+        lowest
+    } else {
+        Some(lowest.map_or(limit, |lowest| lowest.min(limit)))
+    }
+}
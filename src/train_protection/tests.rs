@@ -0,0 +1,131 @@
+use time::Duration;
+use time::macros::datetime;
+use zusi_xml_lib::xml::zusi::result::ResultValue;
+
+use crate::fixtures::{run, sample};
+use crate::result_analyser::AnalyseError;
+use crate::speed_limit::LimitSource;
+use crate::train_protection::{ForcedBrake, TrainProtectionSummary};
+
+fn event(second: i64, fahrt_parameter: u32) -> ResultValue {
+    let ResultValue::FahrtEintrag(mut fahrt_eintrag) = sample(second, -1., -1., None);
+    fahrt_eintrag.fahrt_typ = 8;
+    fahrt_eintrag.fahrt_parameter = fahrt_parameter;
+    ResultValue::FahrtEintrag(fahrt_eintrag)
+}
+
+#[test]
+fn test_acknowledgements() {
+    let analyser = run(vec![
+        sample(0, 0., 20., Some((LimitSource::TrainProtection, 45.8333))),
+        event(1, 48),
+        event(1, 16),
+        sample(2, 40., 20., Some((LimitSource::TrainProtection, 45.8333))),
+        event(3, 48),
+        event(3, 16),
+        event(4, 0),
+        event(4, 0),
+        sample(5, 100., 20., Some((LimitSource::TrainProtection, 45.8333))),
+    ]);
+
+    let train_protection = analyser.train_protection().unwrap();
+    assert_eq!(train_protection.influences, 2);
+    assert_eq!(train_protection.acknowledgements, 2);
+    assert!(train_protection.restrictive_supervisions.is_empty());
+    assert!(train_protection.forced_brakes.is_empty());
+}
+
+#[test]
+fn test_restrictive_supervision_and_forced_brakes() {
+    let analyser = run(vec![
+        sample(0, 0., 23., Some((LimitSource::TrainProtection, 41.6276))),
+        event(2, 192),
+        sample(2, 50., 23., Some((LimitSource::TrainProtection, 32.5354))),
+        event(2, 192),
+        event(3, 64),
+        sample(3, 70., 22.7, Some((LimitSource::TrainProtection, 30.5425))),
+        event(3, 64),
+        event(6, 192),
+        sample(6, 130., 22.5, Some((LimitSource::TrainProtection, 27.4602))),
+        event(6, 192),
+        event(7, 64),
+        event(7, 64),
+        sample(10, 220., 22.1, Some((LimitSource::TrainProtection, 23.6111))),
+        event(12, 0),
+        event(12, 0),
+        sample(14, 300., 21., Some((LimitSource::TrainProtection, 23.6111))),
+    ]);
+
+    let train_protection = analyser.train_protection().unwrap();
+    assert_eq!(train_protection.forced_brakes, vec![
+        ForcedBrake {
+            fahrt_zeit: datetime!(2019-01-01 23:00:02),
+            fahrt_weg: 0.,
+            fahrt_km: 0.,
+            speed: 23.,
+        },
+        ForcedBrake {
+            fahrt_zeit: datetime!(2019-01-01 23:00:06),
+            fahrt_weg: 70.,
+            fahrt_km: 0.07,
+            speed: 22.7,
+        },
+    ]);
+
+    assert_eq!(train_protection.restrictive_supervisions.len(), 1);
+    let supervision = &train_protection.restrictive_supervisions[0];
+    assert_eq!(supervision.start, datetime!(2019-01-01 23:00:02));
+    assert_eq!(supervision.end, datetime!(2019-01-01 23:00:12));
+    assert_eq!(supervision.duration(), Duration::seconds(10));
+    assert_eq!(supervision.distance(), 220.);
+    assert_eq!(supervision.lowest_limit, Some(23.6111));
+    assert_eq!(train_protection.restrictive_supervision_time(), Duration::seconds(10));
+    assert_eq!(train_protection.restrictive_supervision_distance(), 220.);
+}
+
+#[test]
+fn test_restrictive_supervision_until_end() {
+    let analyser = run(vec![
+        sample(0, 0., 10., None),
+        event(1, 64),
+        sample(5, 50., 10., None),
+    ]);
+
+    let train_protection = analyser.train_protection().unwrap();
+    assert_eq!(train_protection.restrictive_supervisions.len(), 1);
+    assert_eq!(train_protection.restrictive_supervisions[0].duration(), Duration::seconds(4));
+    assert_eq!(train_protection.restrictive_supervisions[0].distance(), 50.);
+    assert_eq!(train_protection.restrictive_supervisions[0].lowest_limit, None);
+}
+
+#[test]
+fn test_train_protection_without_samples() {
+    let analyser = run(vec![event(1, 48)]);
+    assert_eq!(analyser.train_protection(), Err(AnalyseError::NoEntries));
+}
+
+#[test]
+fn test_train_protection_summary() {
+    let analyser = run(vec![
+        sample(0, 0., 10., None),
+        event(1, 48),
+        event(1, 16),
+        event(2, 192),
+        sample(5, 50., 10., None),
+        event(6, 0),
+    ]);
+
+    let mut summary: TrainProtectionSummary = (&analyser.train_protection().unwrap()).into();
+    assert_eq!(summary, TrainProtectionSummary {
+        influences: 1,
+        acknowledgements: 1,
+        restrictive_supervisions: 1,
+        restrictive_supervision_time: Duration::seconds(4),
+        restrictive_supervision_distance: 50.,
+        forced_brakes: 1,
+    });
+
+    summary += summary;
+    assert_eq!(summary.forced_brakes, 2);
+    assert_eq!(summary.restrictive_supervision_time, Duration::seconds(8));
+}
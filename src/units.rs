@@ -0,0 +1,60 @@
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum SpeedUnit {
+    #[default]
+    MetresPerSecond,
+    KilometresPerHour,
+}
+
+impl SpeedUnit {
+    /// Converts a value given in SI units.
+    pub fn convert(&self, speed: f32) -> f32 {
+        match self {
+            SpeedUnit::MetresPerSecond => speed,
+            SpeedUnit::KilometresPerHour => speed * 3.6,
+        }
+    }
+
+    /// Short name of the unit usable in identifiers, e.g. in column names.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            SpeedUnit::MetresPerSecond => "ms",
+            SpeedUnit::KilometresPerHour => "kmh",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            SpeedUnit::MetresPerSecond => "m/s",
+            SpeedUnit::KilometresPerHour => "km/h",
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum DistanceUnit {
+    #[default]
+    Metres,
+    Kilometres,
+}
+
+impl DistanceUnit {
+    /// Converts a value given in SI units.
+    pub fn convert(&self, distance: f32) -> f32 {
+        match self {
+            DistanceUnit::Metres => distance,
+            DistanceUnit::Kilometres => distance / 1000.,
+        }
+    }
+
+    /// Short name of the unit usable in identifiers, e.g. in column names.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            DistanceUnit::Metres => "m",
+            DistanceUnit::Kilometres => "km",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        self.suffix()
+    }
+}